@@ -11,7 +11,10 @@ use tui::{
 
 use crate::{
     component::{
+        calendar::Calendar,
+        date_prompt::{DatePrompt, DateSelection, PromptMode},
         filter::Filter,
+        help::Help,
         interval_view::{IntervalView, ReportDuration},
         status::Status,
     },
@@ -21,15 +24,32 @@ use crate::{
 
 use super::component::day::Day;
 
-use self::config::{Config, Key, KeyName};
+use self::{
+    config::{Allocation, Config, Key, KeyName},
+    editor::Edit,
+    keymap::{KeyMap, BINDINGS},
+};
 pub mod config;
+pub mod editor;
+pub mod history;
+pub mod keymap;
 pub mod loader;
 
+/// Request from a view for the application to act on its behalf
+pub enum ViewCommand {
+    ApplyFilter(String),
+    OpenDate(NaiveDate),
+}
+
 enum AppView {
     Day,
     Week,
     Month,
+    Quarter,
     Year,
+    Sprint,
+    Range,
+    Calendar,
 }
 
 pub struct App<'a> {
@@ -37,14 +57,27 @@ pub struct App<'a> {
     loader: Box<dyn loader::Loader + 'a>,
     pub log_days: LogDays,
     pub filtered: LogDays,
+    /// Revision of the filter applied to `filtered`, none when the days
+    /// changed since
+    filtered_revision: Option<u64>,
+    allocation: Allocation,
     day: Day<'a>,
     week: IntervalView<'a>,
     month: IntervalView<'a>,
+    quarter: IntervalView<'a>,
     year: IntervalView<'a>,
+    sprint: IntervalView<'a>,
+    range: IntervalView<'a>,
+    calendar: Calendar<'a>,
     view: AppView,
     pub filter: Filter<'a>,
+    help: Help,
+    date_prompt: DatePrompt<'a>,
+    pub keymap: KeyMap,
     status: Status,
     pub should_quit: bool,
+    /// Timesheet to open in the editor, taken by the main loop
+    pub edit: Option<Edit>,
 }
 
 impl App<'_> {
@@ -57,33 +90,69 @@ impl App<'_> {
         let log_days = LogDays::new(vec![Entry::placeholder()]);
         App {
             filtered: log_days.clone(),
+            filtered_revision: None,
             log_days,
+            allocation: config.allocation,
             loader,
             notification: Notification {
                 level: NotificationLevel::Info,
                 notification: "".to_string(),
                 lifetime: 0,
             },
-            day: Day::new(),
+            day: Day::new(time_factory, config),
             view: AppView::Day,
             week: IntervalView::new(
                 time_factory,
+                config,
                 NaiveDate::from_isoywd(now.year(), now.iso_week().week(), chrono::Weekday::Mon),
                 ReportDuration::Week,
             ),
             month: IntervalView::new(
                 time_factory,
+                config,
                 NaiveDate::from_ymd(now.year(), now.month(), 1),
                 ReportDuration::Month,
             ),
+            quarter: {
+                let mut quarter = IntervalView::new(
+                    time_factory,
+                    config,
+                    now.date(),
+                    ReportDuration::Quarter {
+                        fiscal_year_start: config.fiscal_year_start,
+                    },
+                );
+                quarter.go_to(now.date());
+                quarter
+            },
             year: IntervalView::new(
                 time_factory,
+                config,
                 NaiveDate::from_ymd(now.year(), 1, 1),
                 ReportDuration::Year,
             ),
+            sprint: {
+                let mut sprint = IntervalView::new(
+                    time_factory,
+                    config,
+                    now.date(),
+                    ReportDuration::Sprint {
+                        start: config.sprint.start,
+                        days: config.sprint.days,
+                    },
+                );
+                sprint.go_to(now.date());
+                sprint
+            },
+            range: IntervalView::new(time_factory, config, now.date(), ReportDuration::Day),
+            calendar: Calendar::new(time_factory, config),
             filter: Filter::new(config),
+            help: Help::new(),
+            date_prompt: DatePrompt::new(time_factory),
+            keymap: KeyMap::default(),
             status: Status::new(),
             should_quit: false,
+            edit: None,
         }
     }
 
@@ -103,16 +172,32 @@ impl App<'_> {
             )
             .split(f.size());
 
-        f.render_widget(navigation(), rows[0]);
+        f.render_widget(navigation(&self.keymap), rows[0]);
 
         match self.view {
-            AppView::Day => self.day.draw(f, rows[1], &self.filtered)?,
-            AppView::Week => self.week.draw(f, rows[1], &self.filtered)?,
-            AppView::Month => self.month.draw(f, rows[1], &self.filtered)?,
-            AppView::Year => self.year.draw(f, rows[1], &self.filtered)?,
+            AppView::Day => self.day.draw(f, rows[1], &self.filtered, &self.log_days)?,
+            AppView::Week => self.week.draw(f, rows[1], &self.filtered, &self.log_days)?,
+            AppView::Month => self
+                .month
+                .draw(f, rows[1], &self.filtered, &self.log_days)?,
+            AppView::Quarter => self
+                .quarter
+                .draw(f, rows[1], &self.filtered, &self.log_days)?,
+            AppView::Year => self.year.draw(f, rows[1], &self.filtered, &self.log_days)?,
+            AppView::Sprint => self
+                .sprint
+                .draw(f, rows[1], &self.filtered, &self.log_days)?,
+            AppView::Range => self
+                .range
+                .draw(f, rows[1], &self.filtered, &self.log_days)?,
+            AppView::Calendar => self
+                .calendar
+                .draw(f, rows[1], &self.filtered, &self.log_days)?,
         };
 
         self.filter.draw(f)?;
+        self.date_prompt.draw(f)?;
+        self.help.draw(f, &self.keymap)?;
         self.status.draw(f, rows[2], self)?;
 
         if self.notification.should_display() {
@@ -189,10 +274,26 @@ impl App<'_> {
             }
         };
 
-        self.log_days = LogDays::new(entries);
+        self.log_days = LogDays::new(entries)
+            .with_allocation(self.allocation)
+            .with_summaries_of(&self.log_days);
+        self.filtered_revision = None;
+    }
+
+    /// Reload when a file of the timesheet, or one it includes, changed
+    pub fn reload_changed(&mut self) {
+        if self.loader.changed() {
+            self.reload();
+            self.notify("timesheet changed, reloaded".to_string(), 2);
+        }
     }
 
+    /// Filter the days again if the days or the filter changed since
     pub fn apply_filter(&mut self) {
+        if self.filtered_revision == Some(self.filter.revision) {
+            return;
+        }
+        self.filtered_revision = Some(self.filter.revision);
         if let Some(filter) = &self.filter.filter {
             self.filtered = self.log_days.filter(filter);
             return;
@@ -209,27 +310,124 @@ impl App<'_> {
             self.filter.handle(&key);
             return;
         }
+        if self.date_prompt.visible {
+            match self.date_prompt.handle(&key) {
+                Some(DateSelection::Date(date)) => self.go_to(date),
+                Some(DateSelection::Range(start, end)) => {
+                    self.range.set_range(start, end);
+                    self.set_view(AppView::Range);
+                }
+                None => (),
+            }
+            return;
+        }
+        if self.help.visible {
+            self.help.handle(&key.name);
+            return;
+        }
         match key.name {
             KeyName::Quit => self.should_quit = true,
             KeyName::ToggleFilter => self.filter.show(),
+            KeyName::ToggleHelp => self.help.toggle(),
+            KeyName::GoToDate => self.date_prompt.show(PromptMode::Date),
             KeyName::DayView => self.set_view(AppView::Day),
             KeyName::WeekView => self.set_view(AppView::Week),
             KeyName::MonthView => self.set_view(AppView::Month),
+            KeyName::QuarterView => self.set_view(AppView::Quarter),
             KeyName::YearView => self.set_view(AppView::Year),
+            KeyName::SprintView => self.set_view(AppView::Sprint),
+            KeyName::RangeView => self.date_prompt.show(PromptMode::Range),
+            KeyName::CalendarView => self.set_view(AppView::Calendar),
             KeyName::Reload => {
                 self.reload();
                 self.notify("reloaded timesheet".to_string(), 2);
             }
+            KeyName::OpenEditor if matches!(self.view, AppView::Day) => self.open_editor(),
+            KeyName::CycleAllocation => {
+                self.allocation = self.allocation.next();
+                self.log_days = self.log_days.clone().with_allocation(self.allocation);
+                self.filtered_revision = None;
+                self.notify(format!("allocation: {}", self.allocation), 2);
+            }
             _ => {
-                match self.view {
+                let command = match self.view {
                     AppView::Day => self.day.handle(&key.name),
                     AppView::Week => self.week.handle(&key.name),
                     AppView::Month => self.month.handle(&key.name),
+                    AppView::Quarter => self.quarter.handle(&key.name),
                     AppView::Year => self.year.handle(&key.name),
+                    AppView::Sprint => self.sprint.handle(&key.name),
+                    AppView::Range => self.range.handle(&key.name),
+                    AppView::Calendar => self.calendar.handle(&key.name),
                 };
+                if let Some(command) = command {
+                    self.run(command);
+                }
             }
         };
     }
+
+    /// Move the active view to the given date, the day view shows the
+    /// nearest logged day
+    fn go_to(&mut self, date: NaiveDate) {
+        match self.view {
+            AppView::Day => match self.filtered.nearest_index(date) {
+                Some(index) => {
+                    self.day.go_to(index);
+                    if self.filtered.index_of(date).is_none() {
+                        self.notify(
+                            format!("nothing logged on {}, showing nearest day", date),
+                            2,
+                        );
+                    }
+                }
+                None => self.notify("nothing logged".to_string(), 2),
+            },
+            AppView::Week => self.week.go_to(date),
+            AppView::Month => self.month.go_to(date),
+            AppView::Quarter => self.quarter.go_to(date),
+            AppView::Year => self.year.go_to(date),
+            AppView::Sprint => self.sprint.go_to(date),
+            AppView::Range => self.range.go_to(date),
+            AppView::Calendar => self.calendar.go_to(date),
+        }
+    }
+
+    /// Open the timesheet the selected entry of the day view was read from
+    fn open_editor(&mut self) {
+        let log_day = self
+            .filtered
+            .at(self.day.index.min(self.filtered.len() - 1));
+        let selected = self.day.log_table.scroll.selected();
+        let log = match selected.and_then(|index| log_day.logs().get(index)) {
+            Some(log) => log,
+            None => return self.notify("no entry selected".to_string(), 2),
+        };
+        let path = match log.source() {
+            Some(path) => path,
+            None => return self.error("the entry was not read from a file".to_string(), 2),
+        };
+        match Edit::new(path, log_day.date().naive_date(), log.time_range().start) {
+            Ok(edit) => self.edit = Some(edit),
+            Err(err) => self.error(err.to_string(), 4),
+        }
+    }
+
+    fn run(&mut self, command: ViewCommand) {
+        match command {
+            ViewCommand::ApplyFilter(filter) => {
+                self.filter.apply(&filter);
+                self.notify(format!("filtered by {}", filter), 2);
+            }
+            ViewCommand::OpenDate(date) => match self.filtered.index_of(date) {
+                Some(index) => {
+                    self.day.go_to(index);
+                    self.set_view(AppView::Day);
+                }
+                None => self.notify(format!("nothing logged on {}", date), 2),
+            },
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -256,42 +454,166 @@ impl Notification {
     }
 }
 
-fn navigation<'a>() -> Paragraph<'a> {
-    let text: Vec<Spans> = vec![Spans::from(vec![
-        Span::styled("[p]", Style::default().fg(Color::Green)),
-        Span::raw("rev "),
-        Span::styled("[n]", Style::default().fg(Color::Green)),
-        Span::raw("ext "),
-        Span::styled("[r]", Style::default().fg(Color::Green)),
-        Span::raw("eload "),
-        Span::styled("[d]", Style::default().fg(Color::Green)),
-        Span::raw("ay "),
-        Span::styled("[w]", Style::default().fg(Color::Green)),
-        Span::raw("eek "),
-        Span::styled("[y]", Style::default().fg(Color::Green)),
-        Span::raw("ear "),
-        Span::styled("[f]", Style::default().fg(Color::Green)),
-        Span::raw("ilter"),
-        Span::styled("[q]", Style::default().fg(Color::Green)),
-        Span::raw("uit"),
-    ])];
-
-    Paragraph::new(text)
+fn navigation<'a>(keymap: &KeyMap) -> Paragraph<'a> {
+    let mut spans = vec![];
+    for binding in BINDINGS.iter().filter(|binding| binding.navigation) {
+        let chord = match keymap.chords(binding.action).first() {
+            Some(chord) => chord.to_string(),
+            None => continue,
+        };
+        // abbreviate e.g. "[p] prev" to "[p]rev"
+        let description = match binding.description.strip_prefix(&chord) {
+            Some(rest) => rest.to_string(),
+            None => format!(" {}", binding.description),
+        };
+        spans.push(Span::styled(
+            format!("[{}]", chord),
+            Style::default().fg(Color::Green),
+        ));
+        spans.push(Span::raw(format!("{} ", description)));
+    }
+
+    Paragraph::new(vec![Spans::from(spans)])
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{model::time::FrozenTimeFactory, parser::timesheet::Entries};
+    use std::{fs, time::Instant};
 
-    use super::{loader::FuncLoader, *};
+    use crate::{
+        model::time::FrozenTimeFactory,
+        parser::{timesheet::Entries, token::TokenKind},
+    };
+
+    use super::{
+        loader::{FileLoader, FuncLoader},
+        *,
+    };
 
     #[test]
     pub fn last_day_of_month() {
         App::new(
-            FuncLoader::new(Box::new(|| Entries { entries: vec![] })),
+            FuncLoader::new(Box::new(|| Entries {
+                entries: vec![],
+                includes: vec![],
+            })),
             &Config::empty(),
             &FrozenTimeFactory::new(2022, 1, 1, 12, 0),
             &NaiveDate::from_ymd(2022, 11, 30).and_hms(10, 1, 1),
         );
     }
+
+    #[test]
+    pub fn filters_again_only_when_the_filter_or_the_days_changed() {
+        let config = Config::empty();
+        let time = FrozenTimeFactory::new(2026, 1, 5, 12, 0);
+        let now = NaiveDate::from_ymd(2026, 1, 5).and_hms(12, 0, 0);
+        let mut app = App::new(
+            FuncLoader::new(Box::new(|| Entries {
+                entries: vec![Entry::placeholder()],
+                includes: vec![],
+            })),
+            &config,
+            &time,
+            &now,
+        );
+        let filtered = |app: &App| app.filtered.at(0).logs() as *const _;
+        app.reload();
+        app.filter.apply("@acme");
+        app.apply_filter();
+        let first = filtered(&app);
+        app.apply_filter();
+        assert_eq!(first, filtered(&app));
+
+        app.filter.apply("@review");
+        app.apply_filter();
+        let second = filtered(&app);
+        assert_ne!(first, second);
+        app.reload();
+        app.apply_filter();
+        assert_ne!(second, filtered(&app));
+    }
+
+    /// A timesheet of 50k lines reloaded after changing one day. Only the
+    /// changed day is parsed again and unchanged days and summaries come
+    /// from the cache, which should be well below doing the work again.
+    /// Timings depend on the machine, run it with
+    /// `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore = "timing"]
+    pub fn reloads_large_timesheets_from_the_cache() {
+        let mut timesheet = String::new();
+        let mut date = NaiveDate::from_ymd(2012, 1, 2);
+        let mut days = 0;
+        while days * 10 < 50_000 {
+            timesheet.push_str(&format!("{}\n", date));
+            for hour in 9..17 {
+                timesheet.push_str(&format!(
+                    "{:02}:00-{:02}:00 @acme @review EXAMPLE-{} going through the changes\n",
+                    hour,
+                    hour + 1,
+                    days % 40
+                ));
+            }
+            timesheet.push('\n');
+            date = date.succ();
+            days += 1;
+        }
+        let path = std::env::temp_dir().join(format!("pttlog-bench-{}.log", std::process::id()));
+        fs::write(&path, &timesheet).unwrap();
+
+        let config = Config::empty();
+        let time = FrozenTimeFactory::new(2026, 1, 5, 12, 0);
+        let now = NaiveDate::from_ymd(2026, 1, 5).and_hms(12, 0, 0);
+        let mut app = App::new(
+            FileLoader::new(vec![path.to_string_lossy().to_string()], &config),
+            &config,
+            &time,
+            &now,
+        );
+        let timed = |app: &mut App, run: &dyn Fn(&mut App)| {
+            let started = Instant::now();
+            run(app);
+            started.elapsed()
+        };
+
+        let first_load = timed(&mut app, &|app| app.reload());
+        let first_filter = timed(&mut app, &|app| app.apply_filter());
+        fs::write(
+            &path,
+            format!("{}\n17:00-18:00 @acme overtime\n", timesheet.trim_end()),
+        )
+        .unwrap();
+        let reload = timed(&mut app, &|app| app.reload());
+        let filter = timed(&mut app, &|app| app.apply_filter());
+        let unchanged = timed(&mut app, &|app| app.apply_filter());
+        let summary = timed(&mut app, &|app| {
+            app.log_days.tag_summary(TokenKind::Tag);
+        });
+        let cached_summary = timed(&mut app, &|app| {
+            app.log_days.tag_summary(TokenKind::Tag);
+        });
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(days, app.log_days.len());
+        assert_eq!(9, app.log_days.at(days - 1).logs().len());
+        assert!(
+            reload * 2 < first_load,
+            "reload {:?}, first load {:?}",
+            reload,
+            first_load
+        );
+        assert!(
+            unchanged * 10 < filter.max(first_filter),
+            "unchanged filter {:?}, filter {:?}",
+            unchanged,
+            filter
+        );
+        assert!(
+            cached_summary * 2 < summary,
+            "cached summary {:?}, summary {:?}",
+            cached_summary,
+            summary
+        );
+    }
 }
@@ -1,14 +1,286 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use chrono::{Datelike, NaiveDate};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde_derive::{Deserialize, Serialize};
 
-#[derive(Default, Debug, Serialize, Deserialize)]
+use crate::parser::{timesheet::Tokens, token::TokenKind};
+
+use super::keymap::{KeyChords, KeyMap};
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
+    // values are serialized before tables, as TOML requires
+    /// Hours expected to be worked on a weekday unless `targets` sets the
+    /// hours of each day
+    #[serde(default = "default_daily_target")]
+    pub daily_target: f64,
+    /// Month (1 to 12) in which the fiscal year and its first quarter start
+    #[serde(default = "default_fiscal_year_start")]
+    pub fiscal_year_start: u32,
+    /// Public holidays, which have no target
+    #[serde(default)]
+    pub holidays: Vec<DaySpec>,
+    /// Days of leave, which have no target
+    #[serde(default)]
+    pub leave: Vec<DaySpec>,
+    /// How the duration of an entry is attributed to its tags and tickets
+    #[serde(default)]
+    pub allocation: Allocation,
+    /// What to do with a date found in more than one timesheet
+    #[serde(default)]
+    pub duplicate_dates: DuplicateDates,
     pub projects: Vec<Project>,
+    /// Hours expected per weekday and the start of the flextime balance
+    #[serde(default)]
+    pub targets: Targets,
+    /// Start and length of the sprints shown in the sprint view
+    #[serde(default)]
+    pub sprint: Sprint,
+    /// Rounding of the time billed, none by default
+    #[serde(default)]
+    pub rounding: Option<Rounding>,
+    /// Hours shown on the axis of the timeline
+    #[serde(default)]
+    pub working_hours: WorkingHours,
+    /// Defaults of the invoices written by the `invoice` subcommand
+    #[serde(default)]
+    pub invoice: InvoiceSettings,
+    /// Accounts of the entries exported to a timeclock file
+    #[serde(default)]
+    pub timeclock: Timeclock,
+    /// Key chords by action name, replacing the default bindings
+    #[serde(default)]
+    pub keys: BTreeMap<String, KeyChords>,
+}
+
+fn default_daily_target() -> f64 {
+    8.0
+}
+
+fn default_fiscal_year_start() -> u32 {
+    1
 }
 
 impl Config {
     pub fn empty() -> Config {
-        Config { projects: vec![] }
+        Config {
+            daily_target: default_daily_target(),
+            fiscal_year_start: default_fiscal_year_start(),
+            targets: Targets::default(),
+            holidays: vec![],
+            leave: vec![],
+            allocation: Allocation::default(),
+            duplicate_dates: DuplicateDates::default(),
+            rounding: None,
+            sprint: Sprint::default(),
+            working_hours: WorkingHours::default(),
+            invoice: InvoiceSettings::default(),
+            timeclock: Timeclock::default(),
+            projects: vec![],
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// The project of the first ticket matching a project's prefix, or
+    /// else of the first tag listed in a project's tags
+    pub fn project_for(&self, tokens: &Tokens) -> Option<&Project> {
+        let tokens = tokens.to_vec();
+        tokens
+            .iter()
+            .filter(|token| token.kind == TokenKind::Ticket)
+            .find_map(|token| {
+                self.projects
+                    .iter()
+                    .find(|project| token.text().starts_with(&project.ticket_prefix))
+            })
+            .or_else(|| {
+                tokens
+                    .iter()
+                    .filter(|token| token.kind == TokenKind::Tag)
+                    .find_map(|token| {
+                        self.projects
+                            .iter()
+                            .find(|project| project.tags.iter().any(|tag| tag == token.text()))
+                    })
+            })
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::empty()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Sprint {
+    /// Any day on which a sprint started
+    pub start: NaiveDate,
+    pub days: u32,
+}
+
+impl Default for Sprint {
+    fn default() -> Self {
+        Sprint {
+            start: NaiveDate::from_ymd(2024, 1, 1),
+            days: 14,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Targets {
+    /// First day counted towards the targets and the flextime balance
+    pub start: Option<NaiveDate>,
+    /// Hours for each day of the week, Monday first
+    pub hours: Option<[f64; 7]>,
+}
+
+/// Attribution of an entry's duration to several tags or tickets: the full
+/// duration to each, split evenly or split by weights like `EXAMPLE-1:70%`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Allocation {
+    #[default]
+    Full,
+    Even,
+    Weighted,
+}
+
+/// A date found in more than one timesheet either merges into one day, the
+/// entries in order of their start, or is an error
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateDates {
+    #[default]
+    Merge,
+    Error,
+}
+
+impl Allocation {
+    pub fn next(&self) -> Allocation {
+        match self {
+            Allocation::Full => Allocation::Even,
+            Allocation::Even => Allocation::Weighted,
+            Allocation::Weighted => Allocation::Full,
+        }
+    }
+}
+
+impl Display for Allocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Allocation::Full => "full duration to each",
+            Allocation::Even => "split evenly",
+            Allocation::Weighted => "split by weight",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Rounding {
+    pub mode: RoundingMode,
+    /// Multiple of minutes rounded to, e.g. 6 or 15
+    pub minutes: u32,
+    pub scope: RoundingScope,
+    /// Show the raw time next to the rounded time rather than replacing it
+    #[serde(default)]
+    pub side_by_side: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundingMode {
+    Up,
+    Nearest,
+    Down,
+}
+
+/// What is rounded: each entry, the total of each day, the total of each
+/// ticket or only the total of the period
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundingScope {
+    Entry,
+    Day,
+    Ticket,
+    Interval,
+}
+
+/// A day off: a date (`2026-04-03`), a day of every year (`12-25`) or an
+/// inclusive range of dates (`2026-08-03..2026-08-14`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum DaySpec {
+    Date(NaiveDate),
+    Yearly { month: u32, day: u32 },
+    Range(NaiveDate, NaiveDate),
+}
+
+impl DaySpec {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        match self {
+            DaySpec::Date(day) => *day == date,
+            DaySpec::Yearly { month, day } => date.month() == *month && date.day() == *day,
+            DaySpec::Range(start, end) => date >= *start && date <= *end,
+        }
+    }
+}
+
+impl TryFrom<String> for DaySpec {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let date = |text: &str| {
+            NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+                .map_err(|_| format!("invalid date \"{}\"", text))
+        };
+        if let Some((start, end)) = text.split_once("..") {
+            let (start, end) = (date(start)?, date(end)?);
+            if end < start {
+                return Err(format!("{} is before {}", end, start));
+            }
+            return Ok(DaySpec::Range(start, end));
+        }
+        if let Ok(date) = date(&text) {
+            return Ok(DaySpec::Date(date));
+        }
+        // validate against a leap year so that 02-29 is allowed
+        match NaiveDate::parse_from_str(&format!("2000-{}", text.trim()), "%Y-%m-%d") {
+            Ok(date) => Ok(DaySpec::Yearly {
+                month: date.month(),
+                day: date.day(),
+            }),
+            Err(_) => Err(format!(
+                "invalid day \"{}\", expected YYYY-MM-DD, MM-DD or YYYY-MM-DD..YYYY-MM-DD",
+                text
+            )),
+        }
+    }
+}
+
+impl From<DaySpec> for String {
+    fn from(spec: DaySpec) -> Self {
+        match spec {
+            DaySpec::Date(date) => date.to_string(),
+            DaySpec::Yearly { month, day } => format!("{:02}-{:02}", month, day),
+            DaySpec::Range(start, end) => format!("{}..{}", start, end),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkingHours {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        WorkingHours { start: 0, end: 24 }
     }
 }
 
@@ -17,8 +289,73 @@ pub struct Project {
     pub name: String,
     pub ticket_prefix: String,
     pub tags: Vec<String>,
+    /// Hourly rate billed to the project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<f64>,
+    /// Recipient of the project's invoices, passed on to the invoice as is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InvoiceSettings {
+    pub language: String,
+    pub currency: String,
+    /// Tax rate, e.g. 0.19
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax: Option<f64>,
+    /// Days from issuing until the invoice is due
+    pub due_days: u32,
+    /// Line items per ticket or per day
+    pub items: InvoiceItems,
+    /// Issuer of the invoices, passed on to the invoice as is
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub biller: Option<serde_json::Value>,
+}
+
+impl Default for InvoiceSettings {
+    fn default() -> Self {
+        InvoiceSettings {
+            language: "en".to_string(),
+            currency: "EUR".to_string(),
+            tax: None,
+            due_days: 14,
+            items: InvoiceItems::Ticket,
+            biller: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Timeclock {
+    /// Account of an entry with `{project}`, `{ticket}` and `{tag}` replaced
+    /// by the entry's project and first ticket and tag, leaving out empty
+    /// segments
+    pub account: String,
+    /// Account of the entries without any of the template's values
+    pub fallback: String,
+}
+
+impl Default for Timeclock {
+    fn default() -> Self {
+        Timeclock {
+            account: "{project}:{ticket}".to_string(),
+            fallback: "unassigned".to_string(),
+        }
+    }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum InvoiceItems {
+    #[default]
+    Ticket,
+    Day,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum KeyName {
     NextTab,
     PreviousPage,
@@ -29,9 +366,30 @@ pub enum KeyName {
     DayView,
     WeekView,
     MonthView,
+    QuarterView,
     YearView,
+    SprintView,
+    RangeView,
+    CalendarView,
     ToggleFilter,
+    ToggleHelp,
+    CycleColour,
+    CycleAllocation,
+    OpenEditor,
+    GoToDate,
     PrevTab,
+    SelectNext,
+    SelectPrevious,
+    SelectPageDown,
+    SelectPageUp,
+    SelectFirst,
+    SelectLast,
+    Select,
+    CycleSort,
+    ReverseSort,
+    FocusNext,
+    FocusPrevious,
+    Close,
 }
 
 pub struct Key {
@@ -41,27 +399,76 @@ pub struct Key {
 
 impl Key {
     pub fn for_key_code(code: KeyCode) -> Self {
-        let key = KeyEvent::new(code, KeyModifiers::empty());
-        map_key_event(key)
+        KeyMap::default().map(KeyEvent::new(code, KeyModifiers::empty()))
     }
 }
 
-pub fn map_key_event(key: KeyEvent) -> Key {
-    Key {
-        name: match key.code {
-            KeyCode::Char('q') => KeyName::Quit,
-            KeyCode::Char('r') => KeyName::Reload,
-            KeyCode::Char('n') => KeyName::NextPage,
-            KeyCode::Char('p') => KeyName::PreviousPage,
-            KeyCode::Tab => KeyName::NextTab,
-            KeyCode::BackTab => KeyName::PrevTab,
-            KeyCode::Char('w') => KeyName::WeekView,
-            KeyCode::Char('m') => KeyName::MonthView,
-            KeyCode::Char('d') => KeyName::DayView,
-            KeyCode::Char('y') => KeyName::YearView,
-            KeyCode::Char('f') => KeyName::ToggleFilter,
-            _ => KeyName::Unknown,
-        },
-        event: key,
+#[cfg(test)]
+mod test {
+    use crate::parser::token::Token;
+
+    use super::*;
+
+    #[test]
+    fn project_for_ticket_or_tag() {
+        let config = Config {
+            projects: vec![
+                Project {
+                    name: "acme".to_string(),
+                    ticket_prefix: "ACME-".to_string(),
+                    tags: vec!["acme".to_string()],
+                    rate: None,
+                    recipient: None,
+                },
+                Project {
+                    name: "pttlog".to_string(),
+                    ticket_prefix: "PTT-".to_string(),
+                    tags: vec![],
+                    rate: None,
+                    recipient: None,
+                },
+            ],
+            ..Config::empty()
+        };
+        let project = |tokens: Vec<Token>| {
+            config
+                .project_for(&Tokens::new(tokens))
+                .map(|project| project.name.as_str())
+        };
+
+        assert_eq!(
+            Some("pttlog"),
+            project(vec![Token::ticket("PTT-1".to_string())])
+        );
+        assert_eq!(
+            Some("pttlog"),
+            project(vec![
+                Token::tag("acme".to_string()),
+                Token::ticket("PTT-1".to_string())
+            ])
+        );
+        assert_eq!(Some("acme"), project(vec![Token::tag("acme".to_string())]));
+        assert_eq!(None, project(vec![Token::tag("lunch".to_string())]));
+    }
+
+    #[test]
+    fn parses_day_specs() {
+        let spec = |text: &str| DaySpec::try_from(text.to_string());
+        assert_eq!(
+            Ok(DaySpec::Date(NaiveDate::from_ymd(2026, 4, 3))),
+            spec("2026-04-03")
+        );
+        assert_eq!(Ok(DaySpec::Yearly { month: 2, day: 29 }), spec("02-29"));
+        assert_eq!(
+            Ok(DaySpec::Range(
+                NaiveDate::from_ymd(2026, 8, 3),
+                NaiveDate::from_ymd(2026, 8, 14)
+            )),
+            spec("2026-08-03..2026-08-14")
+        );
+        assert!(spec("2026-08-14..2026-08-03").is_err());
+        assert!(spec("13-01").is_err());
+        assert!(spec("christmas").is_err());
+        assert_eq!("12-25", String::from(spec("12-25").unwrap()));
     }
 }
@@ -0,0 +1,140 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{bail, Result};
+use chrono::{NaiveDate, NaiveTime, Timelike};
+
+/// Timesheet to open in the editor at the line of an entry
+#[derive(Debug, PartialEq)]
+pub struct Edit {
+    pub path: PathBuf,
+    pub line: usize,
+}
+
+impl Edit {
+    /// Locate the entry starting at `start` on `date` in the timesheet, the
+    /// first line when it is not found
+    pub fn new(path: &Path, date: NaiveDate, start: NaiveTime) -> Result<Edit> {
+        let text = match fs::read_to_string(path) {
+            Ok(ok) => ok,
+            Err(err) => bail!("{}: {}", path.display(), err),
+        };
+        Ok(Edit {
+            path: path.to_path_buf(),
+            line: line_of(&text, date, start).unwrap_or(1),
+        })
+    }
+
+    /// Run `$VISUAL`, `$EDITOR` or `vi` on the timesheet and wait for it
+    pub fn run(&self) -> Result<()> {
+        let editor = ["VISUAL", "EDITOR"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|editor| !editor.trim().is_empty())
+            .unwrap_or_else(|| "vi".to_string());
+        // the editor may come with arguments, e.g. "code --wait"
+        let mut words = editor.split_whitespace();
+        let program = words.next().unwrap_or("vi");
+        let status = Command::new(program)
+            .args(words)
+            .arg(format!("+{}", self.line))
+            .arg(&self.path)
+            .status();
+        match status {
+            Ok(status) if status.success() => Ok(()),
+            Ok(status) => bail!("{} {} failed: {}", editor, self.path.display(), status),
+            Err(err) => bail!("{}: {}", editor, err),
+        }
+    }
+}
+
+/// Line number (from 1) of the entry starting at `start` under the `date`
+/// heading, the heading itself when no entry starts then
+fn line_of(text: &str, date: NaiveDate, start: NaiveTime) -> Option<usize> {
+    let mut heading = None;
+    for (number, line) in text.lines().enumerate() {
+        let first = line.split_whitespace().next().unwrap_or("");
+        if let Ok(found) = NaiveDate::parse_from_str(first, "%Y-%m-%d") {
+            if heading.is_some() {
+                break;
+            }
+            if found == date {
+                heading = Some(number + 1);
+            }
+            continue;
+        }
+        if heading.is_some() && starts_at(line, start) {
+            return Some(number + 1);
+        }
+    }
+    heading
+}
+
+fn starts_at(line: &str, start: NaiveTime) -> bool {
+    let time = line
+        .trim_start()
+        .split(|c: char| !(c.is_ascii_digit() || c == ':'))
+        .next()
+        .unwrap_or("");
+    match time.split_once(':') {
+        Some((hour, minute)) => {
+            hour.parse() == Ok(start.hour()) && minute.parse() == Ok(start.minute())
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TIMESHEET: &str = "2026-01-05
+09:00-10:00 @acme planning
+10:00 ACME-1 review
+
+2026-01-06
+9:00-10:00 @acme standup
+10:00-12:30 ACME-2 review
+";
+
+    #[test]
+    fn finds_the_line_of_an_entry() {
+        let date = NaiveDate::from_ymd(2026, 1, 6);
+        assert_eq!(
+            Some(6),
+            line_of(TIMESHEET, date, NaiveTime::from_hms(9, 0, 0))
+        );
+        assert_eq!(
+            Some(7),
+            line_of(TIMESHEET, date, NaiveTime::from_hms(10, 0, 0))
+        );
+        assert_eq!(
+            Some(3),
+            line_of(
+                TIMESHEET,
+                NaiveDate::from_ymd(2026, 1, 5),
+                NaiveTime::from_hms(10, 0, 0)
+            )
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_date_heading() {
+        let date = NaiveDate::from_ymd(2026, 1, 5);
+        assert_eq!(
+            Some(1),
+            line_of(TIMESHEET, date, NaiveTime::from_hms(12, 30, 0))
+        );
+        assert_eq!(
+            None,
+            line_of(
+                TIMESHEET,
+                NaiveDate::from_ymd(2026, 1, 7),
+                NaiveTime::from_hms(9, 0, 0)
+            )
+        );
+    }
+}
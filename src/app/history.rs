@@ -0,0 +1,138 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use directories::ProjectDirs;
+use serde_derive::{Deserialize, Serialize};
+
+const HISTORY_SIZE: usize = 100;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct FilterHistory {
+    pub entries: Vec<String>,
+    #[serde(skip)]
+    position: Option<usize>,
+    #[serde(skip)]
+    draft: String,
+}
+
+impl FilterHistory {
+    pub fn new(entries: Vec<String>) -> FilterHistory {
+        FilterHistory {
+            entries,
+            position: None,
+            draft: "".to_string(),
+        }
+    }
+
+    /// Load the history from its state file
+    pub fn load() -> Result<FilterHistory> {
+        Ok(confy::load_path(path()?)?)
+    }
+
+    pub fn store(&self) -> Result<()> {
+        Ok(confy::store_path(path()?, self)?)
+    }
+
+    /// Add a filter to the end of the history, removing any previous
+    /// occurence and dropping the oldest entries when full.
+    pub fn push(&mut self, filter: &str) {
+        self.reset();
+        let filter = filter.trim();
+        if filter.is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| entry != filter);
+        self.entries.push(filter.to_string());
+
+        if self.entries.len() > HISTORY_SIZE {
+            self.entries.drain(0..self.entries.len() - HISTORY_SIZE);
+        }
+    }
+
+    /// Step back to an older entry, remembering the current input so that it
+    /// can be restored when stepping forward past the newest entry.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let position = match self.position {
+            None => {
+                if self.entries.is_empty() {
+                    return None;
+                }
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(position) => position.saturating_sub(1),
+        };
+        self.position = Some(position);
+        self.entries.get(position).map(String::as_str)
+    }
+
+    pub fn newer(&mut self) -> Option<&str> {
+        let position = self.position?;
+        if position + 1 >= self.entries.len() {
+            self.position = None;
+            return Some(&self.draft);
+        }
+        self.position = Some(position + 1);
+        self.entries.get(position + 1).map(String::as_str)
+    }
+
+    pub fn reset(&mut self) {
+        self.position = None;
+        self.draft = "".to_string();
+    }
+}
+
+/// The history is state rather than config, so it is kept in the state
+/// directory (`$XDG_STATE_HOME/pttlog` on Linux), or in the local data
+/// directory on systems without one
+fn path() -> Result<PathBuf> {
+    let dirs = match ProjectDirs::from("rs", "", "pttlog") {
+        Some(dirs) => dirs,
+        None => bail!("no home directory to keep the filter history in"),
+    };
+    let dir = dirs.state_dir().unwrap_or_else(|| dirs.data_local_dir());
+    Ok(dir.join("history.toml"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn push_deduplicates_entries() {
+        let mut history = FilterHistory::default();
+        history.push("@foo");
+        history.push("@bar");
+        history.push("@foo");
+        history.push("  ");
+        assert_eq!(vec!["@bar", "@foo"], history.entries);
+    }
+
+    #[test]
+    pub fn push_drops_oldest_entries() {
+        let mut history = FilterHistory::default();
+        for i in 0..HISTORY_SIZE + 5 {
+            history.push(&format!("@tag{}", i));
+        }
+        assert_eq!(HISTORY_SIZE, history.entries.len());
+        assert_eq!("@tag5", history.entries[0]);
+    }
+
+    #[test]
+    pub fn navigates_back_and_restores_draft() {
+        let mut history = FilterHistory::new(vec!["@one".to_string(), "@two".to_string()]);
+        assert_eq!(None, history.newer());
+        assert_eq!(Some("@two"), history.older("@dra"));
+        assert_eq!(Some("@one"), history.older("@two"));
+        assert_eq!(Some("@one"), history.older("@one"));
+        assert_eq!(Some("@two"), history.newer());
+        assert_eq!(Some("@dra"), history.newer());
+        assert_eq!(None, history.newer());
+    }
+
+    #[test]
+    pub fn older_with_empty_history() {
+        let mut history = FilterHistory::default();
+        assert_eq!(None, history.older("@foo"));
+    }
+}
@@ -0,0 +1,631 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, Error, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_derive::{Deserialize, Serialize};
+
+use super::config::{Key, KeyName};
+
+/// An action which can be bound to one or more keys
+pub struct Binding {
+    pub action: KeyName,
+    /// Name of the action in the `[keys]` section of the config
+    pub name: &'static str,
+    pub description: &'static str,
+    pub defaults: &'static [&'static str],
+    /// Show the binding in the navigation bar
+    pub navigation: bool,
+    /// Views in which the action is available, used for the help
+    pub scopes: &'static [Scope],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scope {
+    Global,
+    Day,
+    Interval,
+    Calendar,
+    Popup,
+}
+
+impl Scope {
+    pub fn all() -> [Scope; 5] {
+        [
+            Scope::Global,
+            Scope::Day,
+            Scope::Interval,
+            Scope::Calendar,
+            Scope::Popup,
+        ]
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Scope::Global => "Global",
+            Scope::Day => "Day view",
+            Scope::Interval => "Week, month, quarter, year, sprint and range views",
+            Scope::Calendar => "Calendar",
+            Scope::Popup => "Popups",
+        })
+    }
+}
+
+pub const BINDINGS: &[Binding] = &[
+    Binding {
+        action: KeyName::PreviousPage,
+        name: "previous_page",
+        description: "prev",
+        defaults: &["p"],
+        navigation: true,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Calendar],
+    },
+    Binding {
+        action: KeyName::NextPage,
+        name: "next_page",
+        description: "next",
+        defaults: &["n"],
+        navigation: true,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Calendar],
+    },
+    Binding {
+        action: KeyName::Reload,
+        name: "reload",
+        description: "reload",
+        defaults: &["r"],
+        navigation: true,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::DayView,
+        name: "day_view",
+        description: "day",
+        defaults: &["d"],
+        navigation: true,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::WeekView,
+        name: "week_view",
+        description: "week",
+        defaults: &["w"],
+        navigation: true,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::MonthView,
+        name: "month_view",
+        description: "month",
+        defaults: &["m"],
+        navigation: true,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::YearView,
+        name: "year_view",
+        description: "year",
+        defaults: &["y"],
+        navigation: true,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::QuarterView,
+        name: "quarter_view",
+        description: "quarter",
+        defaults: &["Q"],
+        navigation: false,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::SprintView,
+        name: "sprint_view",
+        description: "sprint",
+        defaults: &["i"],
+        navigation: false,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::RangeView,
+        name: "range_view",
+        description: "report on a range of dates",
+        defaults: &["R"],
+        navigation: false,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::CalendarView,
+        name: "calendar_view",
+        description: "calendar",
+        defaults: &["c"],
+        navigation: true,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::ToggleFilter,
+        name: "toggle_filter",
+        description: "filter",
+        defaults: &["f"],
+        navigation: true,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::GoToDate,
+        name: "go_to_date",
+        description: "go to date",
+        defaults: &["t"],
+        navigation: false,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::CycleAllocation,
+        name: "cycle_allocation",
+        description: "cycle allocation of entries with several tags or tickets",
+        defaults: &["a"],
+        navigation: false,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::ToggleHelp,
+        name: "toggle_help",
+        description: "help",
+        defaults: &["?"],
+        navigation: true,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::Quit,
+        name: "quit",
+        description: "quit",
+        defaults: &["q"],
+        navigation: true,
+        scopes: &[Scope::Global],
+    },
+    Binding {
+        action: KeyName::NextTab,
+        name: "next_tab",
+        description: "tabs",
+        defaults: &["tab"],
+        navigation: true,
+        scopes: &[Scope::Interval],
+    },
+    Binding {
+        action: KeyName::PrevTab,
+        name: "previous_tab",
+        description: "previous tab",
+        defaults: &["backtab"],
+        navigation: false,
+        scopes: &[Scope::Interval],
+    },
+    Binding {
+        action: KeyName::SelectNext,
+        name: "select_next",
+        description: "select next row",
+        defaults: &["j", "down"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Calendar, Scope::Popup],
+    },
+    Binding {
+        action: KeyName::SelectPrevious,
+        name: "select_previous",
+        description: "select previous row",
+        defaults: &["k", "up"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Calendar, Scope::Popup],
+    },
+    Binding {
+        action: KeyName::SelectPageDown,
+        name: "select_page_down",
+        description: "page down",
+        defaults: &["pagedown"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Popup],
+    },
+    Binding {
+        action: KeyName::SelectPageUp,
+        name: "select_page_up",
+        description: "page up",
+        defaults: &["pageup"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Popup],
+    },
+    Binding {
+        action: KeyName::SelectFirst,
+        name: "select_first",
+        description: "select first row",
+        defaults: &["g", "home"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Calendar, Scope::Popup],
+    },
+    Binding {
+        action: KeyName::SelectLast,
+        name: "select_last",
+        description: "select last row",
+        defaults: &["G", "end"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Calendar, Scope::Popup],
+    },
+    Binding {
+        action: KeyName::Select,
+        name: "select",
+        description: "open selected row or apply it as filter",
+        defaults: &["enter"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Calendar, Scope::Popup],
+    },
+    Binding {
+        action: KeyName::CycleSort,
+        name: "cycle_sort",
+        description: "change sort column",
+        defaults: &["s"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval],
+    },
+    Binding {
+        action: KeyName::ReverseSort,
+        name: "reverse_sort",
+        description: "reverse sort order",
+        defaults: &["S"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval],
+    },
+    Binding {
+        action: KeyName::FocusNext,
+        name: "focus_next",
+        description: "focus next table",
+        defaults: &["l", "right"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Calendar],
+    },
+    Binding {
+        action: KeyName::FocusPrevious,
+        name: "focus_previous",
+        description: "focus previous table",
+        defaults: &["h", "left"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval, Scope::Calendar],
+    },
+    Binding {
+        action: KeyName::CycleColour,
+        name: "cycle_colour",
+        description: "colour timeline by tag or project",
+        defaults: &["o"],
+        navigation: false,
+        scopes: &[Scope::Day, Scope::Interval],
+    },
+    Binding {
+        action: KeyName::OpenEditor,
+        name: "open_editor",
+        description: "edit the selected entry's timesheet",
+        defaults: &["e"],
+        navigation: false,
+        scopes: &[Scope::Day],
+    },
+    Binding {
+        action: KeyName::Close,
+        name: "close",
+        description: "close",
+        defaults: &["esc"],
+        navigation: false,
+        scopes: &[Scope::Popup],
+    },
+];
+
+/// One or more key chords as written in the config, e.g. `"ctrl+n"` or
+/// `["n", "right"]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyChords {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyChords {
+    fn to_vec(&self) -> Vec<&str> {
+        match self {
+            KeyChords::One(chord) => vec![chord.as_str()],
+            KeyChords::Many(chords) => chords.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// A key code with its modifiers, e.g. `ctrl+alt+left`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Chord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Chord {
+        // shift is implied by upper case characters and back tab
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Chord { code, modifiers }
+    }
+}
+
+impl From<KeyEvent> for Chord {
+    fn from(event: KeyEvent) -> Self {
+        Chord::new(event.code, event.modifiers)
+    }
+}
+
+impl FromStr for Chord {
+    type Err = Error;
+
+    fn from_str(chord: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = chord.split('+').collect();
+        // allow binding the "+" key itself
+        let key = match parts.pop() {
+            Some("") if parts.last() == Some(&"") => {
+                parts.pop();
+                "+"
+            }
+            Some(key) => key,
+            None => bail!("empty key chord"),
+        };
+
+        let mut modifiers = KeyModifiers::empty();
+        for modifier in parts {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => bail!("unknown modifier \"{}\" in key \"{}\"", modifier, chord),
+            };
+        }
+
+        let code = match key.to_lowercase().as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            name if name.len() > 1 && name.starts_with('f') => KeyCode::F(
+                name[1..]
+                    .parse()
+                    .map_err(|_| anyhow!("unknown key \"{}\"", chord))?,
+            ),
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(char), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        KeyCode::Char(char.to_ascii_uppercase())
+                    }
+                    (Some(char), None) => KeyCode::Char(char),
+                    _ => bail!("unknown key \"{}\"", chord),
+                }
+            }
+        };
+
+        Ok(Chord::new(code, modifiers))
+    }
+}
+
+impl Display for Chord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl+"),
+            (KeyModifiers::ALT, "alt+"),
+            (KeyModifiers::SHIFT, "shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(char) => write!(f, "{}", char),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::BackTab => f.write_str("backtab"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// Maps key events to actions using the default bindings, overridden by
+/// the `[keys]` section of the config
+pub struct KeyMap {
+    actions: HashMap<Chord, KeyName>,
+    chords: HashMap<KeyName, Vec<Chord>>,
+}
+
+impl KeyMap {
+    pub fn new(keys: &BTreeMap<String, KeyChords>) -> Result<KeyMap> {
+        for name in keys.keys() {
+            if !BINDINGS.iter().any(|binding| binding.name == name) {
+                bail!("unknown action \"{}\" in [keys] config", name);
+            }
+        }
+
+        let mut actions: HashMap<Chord, KeyName> = HashMap::new();
+        let mut chords: HashMap<KeyName, Vec<Chord>> = HashMap::new();
+
+        for binding in BINDINGS {
+            let configured = match keys.get(binding.name) {
+                Some(chords) => chords.to_vec(),
+                None => binding.defaults.to_vec(),
+            };
+            for chord in configured {
+                let parsed: Chord = chord
+                    .parse()
+                    .map_err(|err: Error| anyhow!("{} (for action \"{}\")", err, binding.name))?;
+                if let Some(existing) = actions.get(&parsed) {
+                    bail!(
+                        "key \"{}\" is bound to both \"{}\" and \"{}\"",
+                        parsed,
+                        binding_for(*existing).name,
+                        binding.name
+                    );
+                }
+                actions.insert(parsed, binding.action);
+                chords.entry(binding.action).or_default().push(parsed);
+            }
+        }
+
+        Ok(KeyMap { actions, chords })
+    }
+
+    pub fn map(&self, event: KeyEvent) -> Key {
+        Key {
+            name: self
+                .actions
+                .get(&Chord::from(event))
+                .copied()
+                .unwrap_or(KeyName::Unknown),
+            event,
+        }
+    }
+
+    pub fn chords(&self, action: KeyName) -> &[Chord] {
+        self.chords.get(&action).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        KeyMap::new(&BTreeMap::new()).expect("default key bindings are valid")
+    }
+}
+
+fn binding_for(action: KeyName) -> &'static Binding {
+    BINDINGS
+        .iter()
+        .find(|binding| binding.action == action)
+        .expect("every action has a binding")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn keys(bindings: &[(&str, &str)]) -> BTreeMap<String, KeyChords> {
+        bindings
+            .iter()
+            .map(|(name, chord)| (name.to_string(), KeyChords::One(chord.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn parses_chords() {
+        for (chord, expected) in [
+            ("q", "q"),
+            ("G", "G"),
+            ("shift+g", "G"),
+            ("ctrl+n", "ctrl+n"),
+            ("Ctrl+Alt+Left", "ctrl+alt+left"),
+            ("pgdn", "pagedown"),
+            ("space", "space"),
+            ("ctrl++", "ctrl++"),
+            ("f5", "f5"),
+        ] {
+            assert_eq!(expected, chord.parse::<Chord>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_chords() {
+        "hyper+q".parse::<Chord>().unwrap_err();
+        "foobar".parse::<Chord>().unwrap_err();
+    }
+
+    #[test]
+    fn maps_events_with_default_bindings() {
+        let keymap = KeyMap::default();
+        assert_eq!(
+            KeyName::Quit,
+            keymap
+                .map(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::empty()))
+                .name
+        );
+        assert_eq!(
+            KeyName::SelectLast,
+            keymap
+                .map(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT))
+                .name
+        );
+        assert_eq!(
+            KeyName::Unknown,
+            keymap
+                .map(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL))
+                .name
+        );
+    }
+
+    #[test]
+    fn overrides_default_bindings() {
+        let keymap = KeyMap::new(&keys(&[("quit", "ctrl+c")])).unwrap();
+        assert_eq!(
+            KeyName::Quit,
+            keymap
+                .map(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+                .name
+        );
+        assert_eq!(
+            KeyName::Unknown,
+            keymap
+                .map(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::empty()))
+                .name
+        );
+        assert_eq!("ctrl+c", keymap.chords(KeyName::Quit)[0].to_string());
+    }
+
+    #[test]
+    fn rejects_conflicting_bindings() {
+        let err = KeyMap::new(&keys(&[("quit", "n")])).err().unwrap();
+        assert_eq!(
+            "key \"n\" is bound to both \"next_page\" and \"quit\"",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_actions() {
+        KeyMap::new(&keys(&[("explode", "x")])).err().unwrap();
+    }
+
+    #[test]
+    fn every_action_has_one_binding() {
+        for binding in BINDINGS {
+            assert_eq!(
+                1,
+                BINDINGS
+                    .iter()
+                    .filter(|other| other.action == binding.action)
+                    .count()
+            );
+        }
+    }
+}
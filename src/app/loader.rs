@@ -1,47 +1,372 @@
 use anyhow::bail;
 use anyhow::Result;
 
-use crate::parser::timesheet::parse_entry;
+use crate::parser::cache::ParseCache;
 use crate::parser::timesheet::Entries;
 use crate::parser::timesheet::Entry;
+use std::cell::RefCell;
 use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::process::Stdio;
+use std::rc::Rc;
+use std::time::SystemTime;
 
 use super::config::Config;
+use super::config::DuplicateDates;
 
 pub trait Loader {
     fn load(&self) -> Result<Entries, anyhow::Error>;
+
+    /// Whether what was loaded changed since, to reload it
+    fn changed(&self) -> bool {
+        false
+    }
 }
 
+/// Loads the timesheets at the paths, which may be files, directories or
+/// globs, and the timesheets they include as one
 pub struct FileLoader<'a> {
-    path: String,
+    paths: Vec<String>,
     config: &'a Config,
+    /// Files and directories of the last load and their modification time
+    loaded: RefCell<Vec<(PathBuf, Option<SystemTime>)>>,
+    cache: RefCell<ParseCache>,
 }
+#[allow(clippy::new_ret_no_self)]
 impl FileLoader<'_> {
-    pub fn new<'a>(path: String, config: &'a Config) -> Box<dyn Loader + 'a> {
-        Box::new(FileLoader { path, config })
+    pub fn new<'a>(paths: Vec<String>, config: &'a Config) -> Box<dyn Loader + 'a> {
+        Box::new(FileLoader {
+            paths,
+            config,
+            loaded: RefCell::new(vec![]),
+            cache: RefCell::new(ParseCache::default()),
+        })
+    }
+
+    /// Days of all timesheets, `read` are the files read even if one of them
+    /// could not be loaded
+    fn read(&self, read: &mut Vec<PathBuf>) -> Result<Vec<Entry>> {
+        let mut cache = self.cache.borrow_mut();
+        let mut entries = vec![];
+        for path in files(&self.paths)? {
+            entries.extend(read_included(
+                &path,
+                self.config,
+                &mut cache,
+                &mut vec![],
+                read,
+            )?);
+        }
+        cache.finish();
+        merge_dates(entries, self.config.duplicate_dates)
     }
 }
 
 impl Loader for FileLoader<'_> {
     fn load(&self) -> Result<Entries, anyhow::Error> {
-        let contents = fs::read_to_string(&self.path)?;
-        let entries = match parse_entry(&contents, self.config) {
-            Ok((_, ok)) => ok,
-            Err(err) => bail!(err.to_string()),
+        let mut read = vec![];
+        let entries = self.read(&mut read);
+
+        // a directory changes when a file is added to it
+        read.extend(
+            self.paths
+                .iter()
+                .map(PathBuf::from)
+                .filter(|path| path.is_dir()),
+        );
+        *self.loaded.borrow_mut() = read
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+
+        Ok(or_placeholder(entries?))
+    }
+
+    fn changed(&self) -> bool {
+        self.loaded
+            .borrow()
+            .iter()
+            .any(|(path, loaded)| modified(path) != *loaded)
+    }
+}
+
+/// The entries, or a placeholder if there are none
+fn or_placeholder(entries: Vec<Entry>) -> Entries {
+    match entries.is_empty() {
+        true => Entries {
+            entries: vec![Entry::placeholder()],
+            includes: vec![],
+        },
+        false => Entries {
+            entries,
+            includes: vec![],
+        },
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// The files at the paths: the files in a directory and the files matching
+/// a glob, in order of their name, each file once
+pub fn files(paths: &[String]) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = vec![];
+    for path in paths {
+        let mut matched = match path.as_str() {
+            dir if Path::new(dir).is_dir() => fs::read_dir(dir)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<PathBuf>, _>>()?
+                .into_iter()
+                .filter(|path| path.is_file() && !is_hidden(path))
+                .collect(),
+            pattern if pattern.contains(['*', '?', '[']) => glob::glob(pattern)?
+                .collect::<Result<Vec<PathBuf>, _>>()?
+                .into_iter()
+                .filter(|path| path.is_file())
+                .collect(),
+            file => vec![PathBuf::from(file)],
         };
+        if matched.is_empty() {
+            bail!("no timesheet in {}", path);
+        }
+        matched.sort();
+        for file in matched {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+/// Days of the timesheet and of the timesheets it includes, relative to it,
+/// with their logs pointing back to their file. `including` are the files
+/// including this one, to detect cycles, and `read` the files read so far,
+/// each file is read once.
+fn read_included(
+    path: &Path,
+    config: &Config,
+    cache: &mut ParseCache,
+    including: &mut Vec<PathBuf>,
+    read: &mut Vec<PathBuf>,
+) -> Result<Vec<(Rc<Path>, Entry)>> {
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if let Some(start) = including.iter().position(|file| *file == canonical) {
+        let cycle: Vec<String> = including[start..]
+            .iter()
+            .chain([&canonical])
+            .map(|file| file.display().to_string())
+            .collect();
+        bail!("include cycle: {}", cycle.join(" -> "));
+    }
+    if read.contains(&canonical) {
+        return Ok(vec![]);
+    }
+    read.push(canonical.clone());
+
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) => bail!("{}: {}", path.display(), err),
+    };
+    let parsed = match cache.parse(&contents, config) {
+        Ok(parsed) => parsed,
+        Err(err) => bail!("{}: {}", path.display(), err),
+    };
+    let source: Rc<Path> = Rc::from(path);
+    let mut entries: Vec<(Rc<Path>, Entry)> = parsed
+        .entries
+        .into_iter()
+        .map(|mut entry| {
+            for log in entry.logs.iter_mut() {
+                log.source = Some(source.clone());
+            }
+            (source.clone(), entry)
+        })
+        .collect();
+
+    including.push(canonical);
+    let dir = path.parent().unwrap_or(Path::new(""));
+    for include in parsed.includes {
+        entries.extend(read_included(
+            &dir.join(include),
+            config,
+            cache,
+            including,
+            read,
+        )?);
+    }
+    including.pop();
+    Ok(entries)
+}
+
+/// Days in order of their date, a date found more than once is merged into
+/// one day with the logs in order of their start, or is an error if found
+/// in different files and merging is not wanted
+fn merge_dates(
+    mut entries: Vec<(Rc<Path>, Entry)>,
+    duplicates: DuplicateDates,
+) -> Result<Vec<Entry>> {
+    entries.sort_by_key(|(_, entry)| entry.date.sort_value());
+    let mut merged: Vec<(Rc<Path>, Entry)> = vec![];
+    for (source, entry) in entries {
+        let last = match merged.last_mut() {
+            Some((last_source, last)) if last.date_object() == entry.date_object() => {
+                if duplicates == DuplicateDates::Error && *last_source != source {
+                    bail!(
+                        "{} is in both {} and {}",
+                        entry.date,
+                        last_source.display(),
+                        source.display()
+                    );
+                }
+                last
+            }
+            _ => {
+                merged.push((source, entry));
+                continue;
+            }
+        };
+        last.off = last.off.or(entry.off);
+        last.logs.extend(entry.logs);
+        last.logs.sort_by_key(|log| log.time.start.time());
+    }
+    Ok(merged.into_iter().map(|(_, entry)| entry).collect())
+}
+
+/// Loads a timesheet read from stdin, e.g. `gpg -d timesheet.gpg |
+/// pttlogger -`. Stdin is read once, before the terminal is taken over, so
+/// reloading parses the same text again.
+pub struct StdinLoader<'a> {
+    text: String,
+    config: &'a Config,
+    cache: RefCell<ParseCache>,
+}
+#[allow(clippy::new_ret_no_self)]
+impl StdinLoader<'_> {
+    pub fn new<'a>(config: &'a Config) -> Result<Box<dyn Loader + 'a>> {
+        let text = io::read_to_string(io::stdin())?;
+        Ok(Box::new(StdinLoader {
+            text,
+            config,
+            cache: RefCell::new(ParseCache::default()),
+        }))
+    }
+}
 
-        if entries.entries.is_empty() {
-            return Ok(Entries {
-                entries: vec![Entry::placeholder()],
-            });
+impl Loader for StdinLoader<'_> {
+    fn load(&self) -> Result<Entries, anyhow::Error> {
+        load_text(
+            &self.text,
+            "stdin",
+            self.config,
+            &mut self.cache.borrow_mut(),
+        )
+    }
+}
+
+/// Loads the output of a shell command, which is run again on each reload
+pub struct CommandLoader<'a> {
+    command: String,
+    config: &'a Config,
+    cache: RefCell<ParseCache>,
+}
+#[allow(clippy::new_ret_no_self)]
+impl CommandLoader<'_> {
+    pub fn new<'a>(command: String, config: &'a Config) -> Box<dyn Loader + 'a> {
+        Box::new(CommandLoader {
+            command,
+            config,
+            cache: RefCell::new(ParseCache::default()),
+        })
+    }
+}
+
+impl Loader for CommandLoader<'_> {
+    fn load(&self) -> Result<Entries, anyhow::Error> {
+        let output = match shell(&self.command).stdin(Stdio::null()).output() {
+            Ok(output) => output,
+            Err(err) => bail!("{}: {}", self.command, err),
+        };
+        if !output.status.success() {
+            bail!(
+                "{}: {} {}",
+                self.command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
         }
-        Ok(entries)
+        let text = String::from_utf8(output.stdout)?;
+        load_text(
+            &text,
+            &self.command,
+            self.config,
+            &mut self.cache.borrow_mut(),
+        )
+    }
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> process::Command {
+    let mut shell = process::Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> process::Command {
+    let mut shell = process::Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+/// Days of a timesheet not read from a file, its includes are relative to
+/// the working directory
+fn load_text(text: &str, name: &str, config: &Config, cache: &mut ParseCache) -> Result<Entries> {
+    let parsed = match cache.parse(text, config) {
+        Ok(parsed) => parsed,
+        Err(err) => bail!("{}: {}", name, err),
+    };
+    let source: Rc<Path> = Rc::from(Path::new(name));
+    let mut entries: Vec<(Rc<Path>, Entry)> = parsed
+        .entries
+        .into_iter()
+        .map(|entry| (source.clone(), entry))
+        .collect();
+    let mut read = vec![];
+    for include in parsed.includes {
+        entries.extend(read_included(
+            Path::new(&include),
+            config,
+            cache,
+            &mut vec![],
+            &mut read,
+        )?);
     }
+    cache.finish();
+    Ok(or_placeholder(merge_dates(
+        entries,
+        config.duplicate_dates,
+    )?))
 }
 
 pub struct FuncLoader {
     pub factory: Box<dyn Fn() -> Entries>,
 }
+#[allow(clippy::new_ret_no_self)]
 impl FuncLoader {
     pub fn new(func: Box<dyn Fn() -> Entries>) -> Box<dyn Loader> {
         Box::new(FuncLoader { factory: func })
@@ -53,3 +378,202 @@ impl Loader for FuncLoader {
         Ok((self.factory)())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Directory with the timesheets, emptied first
+    fn timesheets(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pttlog-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, contents) in files {
+            fs::write(dir.join(file), contents).unwrap();
+        }
+        dir
+    }
+
+    fn load(paths: &[&Path], config: &Config) -> Result<Entries> {
+        let paths = paths
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        FileLoader::new(paths, config).load()
+    }
+
+    #[test]
+    fn loads_directories_and_globs() {
+        let dir = timesheets(
+            "globs",
+            &[
+                ("2026.log", "2026-01-05\n09:00-10:00 new year\n"),
+                ("2025.log", "2025-12-30\n09:00-10:00 old year\n"),
+                (".2025.log.swp", "garbage"),
+                ("notes.txt", "2024-01-01\n09:00-10:00 notes\n"),
+            ],
+        );
+        let dates = |entries: Entries| -> Vec<String> {
+            entries
+                .entries
+                .iter()
+                .map(|entry| entry.date.to_string())
+                .collect()
+        };
+        let config = Config::empty();
+        assert_eq!(
+            vec!["2024-01-01", "2025-12-30", "2026-01-05"],
+            dates(load(&[&dir], &config).unwrap())
+        );
+        assert_eq!(
+            vec!["2025-12-30", "2026-01-05"],
+            dates(load(&[&dir.join("*.log"), &dir.join("2026.log")], &config).unwrap())
+        );
+        let entries = load(&[&dir.join("2026.log")], &config).unwrap();
+        assert_eq!(
+            Some(dir.join("2026.log").as_path()),
+            entries.entries[0].logs[0].source.as_deref()
+        );
+        assert_eq!(
+            format!("no timesheet in {}", dir.join("*.org").display()),
+            load(&[&dir.join("*.org")], &config)
+                .unwrap_err()
+                .to_string()
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn merges_or_rejects_the_same_date_in_two_files() {
+        let dir = timesheets(
+            "duplicates",
+            &[
+                (
+                    "acme.log",
+                    "2026-01-05\n09:00-10:00 @acme\n13:00-14:00 @acme\n",
+                ),
+                ("other.log", "2026-01-05\n10:00-12:00 @other\n"),
+            ],
+        );
+        let mut config = Config::empty();
+        let entries = load(&[&dir], &config).unwrap().entries;
+        assert_eq!(1, entries.len());
+        assert_eq!(
+            vec!["09:00 acme.log", "10:00 other.log", "13:00 acme.log"],
+            entries[0]
+                .logs
+                .iter()
+                .map(|log| format!(
+                    "{} {}",
+                    log.time.start.time().format("%H:%M"),
+                    log.source
+                        .as_ref()
+                        .unwrap()
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                ))
+                .collect::<Vec<String>>()
+        );
+
+        config.duplicate_dates = DuplicateDates::Error;
+        assert_eq!(
+            format!(
+                "2026-01-05 is in both {} and {}",
+                dir.join("acme.log").display(),
+                dir.join("other.log").display()
+            ),
+            load(&[&dir], &config).unwrap_err().to_string()
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn includes_timesheets_relative_to_the_including_one() {
+        let dir = timesheets(
+            "includes",
+            &[
+                (
+                    "main.log",
+                    "include archive/2025.log\n\n2026-01-05\n09:00-10:00 new year\n",
+                ),
+                ("shared.log", "2024-06-01\n09:00-10:00 shared\n"),
+            ],
+        );
+        fs::create_dir_all(dir.join("archive")).unwrap();
+        fs::write(
+            dir.join("archive/2025.log"),
+            "2025-12-30\n09:00-10:00 old year\n\n!include ../shared.log\n",
+        )
+        .unwrap();
+        let config = Config::empty();
+        let loader = FileLoader::new(
+            vec![dir.join("main.log").to_string_lossy().to_string()],
+            &config,
+        );
+        let entries = loader.load().unwrap().entries;
+        assert_eq!(
+            vec!["2024-06-01", "2025-12-30", "2026-01-05"],
+            entries
+                .iter()
+                .map(|entry| entry.date.to_string())
+                .collect::<Vec<String>>()
+        );
+        assert_eq!(
+            Some(dir.join("archive/2025.log").as_path()),
+            entries[1].logs[0].source.as_deref()
+        );
+
+        assert!(!loader.changed());
+        let file = fs::File::options()
+            .write(true)
+            .open(dir.join("shared.log"))
+            .unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH).unwrap();
+        assert!(loader.changed());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_include_cycles() {
+        let dir = timesheets(
+            "cycles",
+            &[
+                ("a.log", "include b.log\n"),
+                ("b.log", "2026-01-05\n09:00-10:00 b\n\ninclude a.log\n"),
+            ],
+        );
+        let canonical = fs::canonicalize(&dir).unwrap();
+        assert_eq!(
+            format!(
+                "include cycle: {} -> {} -> {}",
+                canonical.join("a.log").display(),
+                canonical.join("b.log").display(),
+                canonical.join("a.log").display()
+            ),
+            load(&[&dir.join("a.log")], &Config::empty())
+                .unwrap_err()
+                .to_string()
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn loads_the_output_of_a_command() {
+        let config = Config::empty();
+        let loader = CommandLoader::new(
+            "printf '2026-01-05\\n09:00-10:00 @acme\\n'".to_string(),
+            &config,
+        );
+        let entries = loader.load().unwrap().entries;
+        assert_eq!("2026-01-05", entries[0].date.to_string());
+        assert_eq!(None, entries[0].logs[0].source);
+
+        let loader = CommandLoader::new("echo 'no secret key' >&2; exit 2".to_string(), &config);
+        assert_eq!(
+            "echo 'no secret key' >&2; exit 2: exit status: 2 no secret key",
+            loader.load().unwrap_err().to_string()
+        );
+    }
+}
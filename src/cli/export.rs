@@ -0,0 +1,58 @@
+use std::fs;
+
+use anyhow::Result;
+use chrono::{Local, Utc};
+use clap::{Args, ValueEnum};
+
+use crate::{
+    app::config::Config,
+    export::{ics::ics, timeclock::timeclock},
+    parser::{date::parse_date_range, filter::parse_filter},
+};
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Timesheets, directories or globs to export
+    #[arg(required = true)]
+    paths: Vec<String>,
+    #[arg(long, value_enum)]
+    format: ExportFormat,
+    /// Dates exported, e.g. 2026-01-01..2026-01-31 or -4w..today, by default
+    /// all of them
+    #[arg(long)]
+    range: Option<String>,
+    /// Only the entries matching the filter, e.g. "AND @acme NOT @meeting"
+    #[arg(long)]
+    filter: Option<String>,
+    /// File written, by default stdout
+    #[arg(short, long)]
+    output: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    /// hledger timeclock `i` and `o` lines
+    Timeclock,
+    /// iCalendar with an event for each entry
+    Ics,
+}
+
+pub fn run(args: ExportArgs, config: &Config) -> Result<()> {
+    let mut log_days = super::load(&args.paths, config)?;
+    if let Some(range) = &args.range {
+        let (start, end) = parse_date_range(range, Local::now().date_naive())?;
+        log_days = log_days.until(start, end);
+    }
+    if let Some(filter) = &args.filter {
+        log_days = log_days.filter(&parse_filter(filter, config)?);
+    }
+    let contents = match args.format {
+        ExportFormat::Timeclock => timeclock(config, &log_days),
+        ExportFormat::Ics => ics(&log_days, Utc::now()),
+    };
+    match args.output {
+        Some(output) => fs::write(output, contents)?,
+        None => print!("{}", contents),
+    }
+    Ok(())
+}
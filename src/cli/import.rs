@@ -0,0 +1,94 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+use anyhow::{bail, Result};
+use chrono::Local;
+use clap::{Args, ValueEnum};
+
+use crate::{
+    app::config::Config,
+    import::{self, merge, org, timewarrior, tracker_csv},
+};
+
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    /// Timesheet merged into, created if it does not exist
+    path: String,
+    /// Files exported from another tool
+    #[arg(required = true)]
+    files: Vec<String>,
+    /// Format of the files, by default from their extension
+    #[arg(long, value_enum)]
+    format: Option<ImportFormat>,
+    /// Show the entries that would be added without changing the timesheet
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ImportFormat {
+    /// Timewarrior data files (`.data`)
+    Timewarrior,
+    /// Toggl or Clockify detailed CSV exports (`.csv`)
+    #[value(alias = "toggl", alias = "clockify")]
+    Csv,
+    /// Org-mode files with `CLOCK:` lines (`.org`)
+    Org,
+}
+
+impl ImportFormat {
+    fn for_path(path: &str) -> Option<ImportFormat> {
+        match Path::new(path).extension()?.to_str()? {
+            "data" => Some(ImportFormat::Timewarrior),
+            "csv" => Some(ImportFormat::Csv),
+            "org" => Some(ImportFormat::Org),
+            _ => None,
+        }
+    }
+}
+
+pub fn run(args: ImportArgs, config: &Config) -> Result<()> {
+    let mut clocked = vec![];
+    for file in args.files.iter() {
+        let format = match args.format.or_else(|| ImportFormat::for_path(file)) {
+            Some(format) => format,
+            None => bail!("unknown format of {}, use --format", file),
+        };
+        let text = fs::read_to_string(file)?;
+        let read = match format {
+            ImportFormat::Timewarrior => timewarrior::read(&text, config, &Local),
+            ImportFormat::Csv => tracker_csv::read(&text, config),
+            ImportFormat::Org => org::read(&text, config),
+        };
+        match read {
+            Ok(read) => clocked.extend(read),
+            Err(err) => bail!("{}: {}", file, err),
+        }
+    }
+
+    let timesheet = match fs::read_to_string(&args.path) {
+        Ok(timesheet) => timesheet,
+        Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let merged = merge::merge(&timesheet, &import::by_date(&clocked));
+    if args.dry_run {
+        print!("{}", merge::diff(&merged));
+        return Ok(());
+    }
+    let added = merged
+        .iter()
+        .filter(|line| line.is_added() && !line.text.is_empty())
+        .count();
+    if added > 0 {
+        fs::write(&args.path, merge::to_string(&merged))?;
+    }
+    eprintln!("added {} lines to {}", added, args.path);
+    let left_out = merged
+        .iter()
+        .filter(|line| matches!(line.change, merge::Change::LeftOut(_)))
+        .count();
+    if left_out > 0 {
+        eprintln!("left out {} conflicting lines, see --dry-run", left_out);
+    }
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::{fs, io::ErrorKind, path::PathBuf, process};
+
+use anyhow::{bail, Result};
+use chrono::{Duration, Local};
+use clap::Args;
+
+use crate::{
+    app::config::{Config, InvoiceItems},
+    export::invoice::{Format, Invoice},
+    parser::date::parse_date_range,
+};
+
+#[derive(Args, Debug)]
+pub struct InvoiceArgs {
+    /// Name of a configured project
+    project: String,
+    /// Dates invoiced, e.g. 2026-01-01..2026-01-31 or -4w..today
+    range: String,
+    /// Timesheets, directories or globs to invoice
+    #[arg(required = true)]
+    paths: Vec<String>,
+    /// Invoice number, by default the project and the month invoiced
+    #[arg(long)]
+    id: Option<String>,
+    /// Line items per ticket or per day, by default as configured
+    #[arg(long, value_enum)]
+    items: Option<InvoiceItems>,
+    /// Format of the metadata
+    #[arg(long, value_enum, default_value_t = Format::Yaml)]
+    format: Format,
+    /// File written, `-` for stdout. By default the invoice number, next to
+    /// the template when compiling
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Typst file to compile with `typst compile` once the metadata is written
+    #[arg(long)]
+    compile: Option<PathBuf>,
+}
+
+pub fn run(args: InvoiceArgs, config: &Config) -> Result<()> {
+    let project = match config
+        .projects
+        .iter()
+        .find(|project| project.name == args.project)
+    {
+        Some(project) => project,
+        None => bail!("no project \"{}\" configured", args.project),
+    };
+    let today = Local::now().date_naive();
+    let (start, end) = parse_date_range(&args.range, today)?;
+    let id = args.id.unwrap_or_else(|| {
+        format!(
+            "{}-{}",
+            project.name,
+            (end - Duration::days(1)).format("%Y-%m")
+        )
+    });
+    let log_days = super::load(&args.paths, config)?;
+    let invoice = Invoice::new(
+        config,
+        &log_days,
+        project,
+        id.to_string(),
+        (start, end),
+        args.items.unwrap_or(config.invoice.items),
+        today,
+    )?;
+    let contents = invoice.to_string(args.format)?;
+
+    let output = match (args.output, &args.compile) {
+        (Some(output), _) => output,
+        (None, Some(template)) => template.with_file_name(&id),
+        (None, None) => PathBuf::from(&id),
+    };
+    if output.as_os_str() == "-" {
+        print!("{}", contents);
+    } else {
+        fs::write(&output, contents)?;
+        eprintln!("wrote {}", output.display());
+    }
+
+    if let Some(template) = args.compile {
+        match process::Command::new("typst")
+            .arg("compile")
+            .arg(&template)
+            .status()
+        {
+            Ok(status) if status.success() => eprintln!("compiled {}", template.display()),
+            Ok(status) => bail!("typst compile {} failed: {}", template.display(), status),
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                bail!(
+                    "typst is not installed, compile {} yourself",
+                    template.display()
+                )
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::{
+    app::{
+        config::Config,
+        loader::{FileLoader, StdinLoader},
+    },
+    model::model::LogDays,
+};
+
+pub mod export;
+pub mod import;
+pub mod invoice;
+pub mod report;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Write the metadata of an invoice for a project
+    Invoice(invoice::InvoiceArgs),
+    /// Merge time tracked in Timewarrior, Toggl, Clockify or org-mode into a
+    /// timesheet
+    Import(import::ImportArgs),
+    /// Export the entries of a timesheet to another format
+    Export(export::ExportArgs),
+    /// Render the summary and the list of days as markdown or HTML
+    Report(report::ReportArgs),
+}
+
+impl Command {
+    pub fn run(self, config: &Config) -> Result<()> {
+        match self {
+            Command::Invoice(args) => invoice::run(args, config),
+            Command::Import(args) => import::run(args, config),
+            Command::Export(args) => export::run(args, config),
+            Command::Report(args) => report::run(args, config),
+        }
+    }
+}
+
+/// The days of the timesheets, directories and globs at the paths, or of
+/// stdin for `-`, attributed as configured
+fn load(paths: &[String], config: &Config) -> Result<LogDays> {
+    let loader = match paths {
+        [path] if path == "-" => StdinLoader::new(config)?,
+        paths => FileLoader::new(paths.to_vec(), config),
+    };
+    let entries = loader.load()?.entries;
+    Ok(LogDays::new(entries).with_allocation(config.allocation))
+}
@@ -0,0 +1,63 @@
+use std::fs;
+
+use anyhow::Result;
+use chrono::{Datelike, Local, Months};
+use clap::{Args, ValueEnum};
+
+use crate::{
+    app::config::Config,
+    export::report::Report,
+    parser::{date::parse_date_range, filter::parse_filter},
+};
+
+#[derive(Args, Debug)]
+pub struct ReportArgs {
+    /// Timesheets, directories or globs to report on
+    #[arg(required = true)]
+    paths: Vec<String>,
+    #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+    format: ReportFormat,
+    /// Dates reported, e.g. 2026-01-01..2026-01-31 or -4w..today, by default
+    /// the current month
+    #[arg(long)]
+    range: Option<String>,
+    /// Only the entries matching the filter, e.g. "AND @acme NOT @meeting"
+    #[arg(long)]
+    filter: Option<String>,
+    /// File written, by default stdout
+    #[arg(short, long)]
+    output: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    /// Markdown tables
+    Markdown,
+    /// A self-contained HTML page with a chart of the weekdays
+    Html,
+}
+
+pub fn run(args: ReportArgs, config: &Config) -> Result<()> {
+    let today = Local::now().date_naive();
+    let range = match &args.range {
+        Some(range) => parse_date_range(range, today)?,
+        None => {
+            let start = today.with_day(1).expect("first of the month");
+            (start, start + Months::new(1))
+        }
+    };
+    let mut log_days = super::load(&args.paths, config)?;
+    if let Some(filter) = &args.filter {
+        log_days = log_days.filter(&parse_filter(filter, config)?);
+    }
+    let report = Report::new(config, &log_days, range, today);
+    let contents = match args.format {
+        ReportFormat::Markdown => report.to_markdown(),
+        ReportFormat::Html => report.to_html(),
+    };
+    match args.output {
+        Some(output) => fs::write(output, contents)?,
+        None => print!("{}", contents),
+    }
+    Ok(())
+}
@@ -0,0 +1,274 @@
+use anyhow::{Error, Result};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::{
+    app::{
+        config::{Config, KeyName},
+        ViewCommand,
+    },
+    model::{
+        model::{LogDays, LogDuration},
+        target::Schedule,
+        time::TimeFactory,
+    },
+};
+
+/// Month grid showing the time logged on each day, coloured by how it
+/// compares to the day's target
+pub struct Calendar<'a> {
+    time: &'a dyn TimeFactory,
+    config: &'a Config,
+    selected: NaiveDate,
+}
+
+const HEAT: &[(f64, Color, &str)] = &[
+    (0.5, Color::Red, "<50%"),
+    (0.9, Color::Yellow, "<90%"),
+    (1.1, Color::Green, "on target"),
+    (f64::MAX, Color::Magenta, ">110%"),
+];
+
+const DAY_OFF: Color = Color::Blue;
+
+const MISSED: Color = Color::LightRed;
+
+impl Calendar<'_> {
+    pub fn new<'a>(time: &'a dyn TimeFactory, config: &'a Config) -> Calendar<'a> {
+        Calendar {
+            selected: time.now().date(),
+            time,
+            config,
+        }
+    }
+
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        area: Rect,
+        log_days: &LogDays,
+        unfiltered: &LogDays,
+    ) -> Result<(), Error> {
+        let minutes = log_days.minutes_by_date();
+        // a day is missed whatever the filter, from the first day logged
+        let logged_dates = unfiltered.minutes_by_date();
+        let first_logged = logged_dates.keys().min().copied();
+        let schedule = Schedule::new(self.config, log_days);
+        let first = self.selected.with_day(1).unwrap();
+        let last = first + Months::new(1) - Duration::days(1);
+        let month_total = minutes
+            .iter()
+            .filter(|(date, _)| **date >= first && **date <= last)
+            .map(|(_, minutes)| minutes)
+            .sum();
+
+        f.render_widget(
+            Block::default().borders(Borders::ALL).title(format!(
+                "{} {}",
+                first.format("%B %Y"),
+                LogDuration::from_minutes(month_total)
+            )),
+            area,
+        );
+
+        let rows = Layout::default()
+            .direction(tui::layout::Direction::Vertical)
+            .constraints([Constraint::Min(4), Constraint::Length(1)])
+            .split(area.inner(&Margin {
+                vertical: 2,
+                horizontal: 2,
+            }));
+
+        let grid_start = first - Duration::days(first.weekday().num_days_from_monday() as i64);
+        let weeks = ((last - grid_start).num_days() / 7 + 1) as u16;
+        let cell_height = (rows[0].height.saturating_sub(2) / weeks).clamp(2, 4);
+
+        let headers = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+            .map(|day| Cell::from(Span::styled(day, Style::default().fg(Color::DarkGray))));
+
+        let today = self.time.now().date();
+        let weeks = (0..weeks).map(|week| {
+            Row::new((0..7).map(|weekday| {
+                let date = grid_start + Duration::days((week * 7 + weekday) as i64);
+                if date.month() != first.month() {
+                    return Cell::default();
+                }
+                let logged = minutes.get(&date).copied().unwrap_or(0);
+                let off = schedule.day_off(date);
+                let missed = date < today
+                    && first_logged.is_some_and(|first| date >= first)
+                    && !logged_dates.contains_key(&date);
+                let heat = heat(logged, schedule.target_minutes(date), missed);
+                let mut style = match (heat, off) {
+                    (Some(color), _) => Style::default().bg(color).fg(Color::Black),
+                    (None, Some(_)) => Style::default().bg(DAY_OFF).fg(Color::Black),
+                    (None, None) => Style::default(),
+                };
+                if date == self.selected {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                let mut day_style = Style::default();
+                if date == today {
+                    day_style = day_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+                }
+                Cell::from(Text::from(vec![
+                    Spans::from(vec![
+                        Span::styled(format!("{:>2}", date.day()), day_style),
+                        Span::raw(off.map(|off| format!(" {}", off)).unwrap_or_default()),
+                    ]),
+                    Spans::from(match logged {
+                        0 => String::new(),
+                        _ => LogDuration::from_minutes(logged).to_string(),
+                    }),
+                ]))
+                .style(style)
+            }))
+            .height(cell_height)
+        });
+
+        let table = Table::new(weeks)
+            .header(Row::new(headers).height(1).bottom_margin(1))
+            .widths(&[Constraint::Ratio(1, 7); 7]);
+        f.render_widget(table, rows[0]);
+        let monday =
+            self.selected - Duration::days(self.selected.weekday().num_days_from_monday() as i64);
+        let week_target = (0..7)
+            .map(|day| schedule.target_minutes(monday + Duration::days(day)))
+            .sum();
+        f.render_widget(legend(week_target), rows[1]);
+
+        Ok(())
+    }
+
+    fn select(&mut self, date: NaiveDate) {
+        // do not navigate into the future beyond the current month
+        let today = self.time.now().date();
+        if date > today && date.with_day(1) > today.with_day(1) {
+            return;
+        }
+        self.selected = date;
+    }
+
+    pub fn go_to(&mut self, date: NaiveDate) {
+        self.selected = date.min(self.time.now().date());
+    }
+
+    pub(crate) fn handle(&mut self, key: &KeyName) -> Option<ViewCommand> {
+        let selected = self.selected;
+        match key {
+            KeyName::PreviousPage => self.select(selected - Months::new(1)),
+            KeyName::NextPage => self.select(selected + Months::new(1)),
+            KeyName::FocusPrevious => self.select(selected - Duration::days(1)),
+            KeyName::FocusNext => self.select(selected + Duration::days(1)),
+            KeyName::SelectPrevious => self.select(selected - Duration::days(7)),
+            KeyName::SelectNext => self.select(selected + Duration::days(7)),
+            KeyName::SelectFirst => self.select(selected.with_day(1).unwrap()),
+            KeyName::SelectLast => {
+                self.select(selected.with_day(1).unwrap() + Months::new(1) - Duration::days(1))
+            }
+            KeyName::Select => return Some(ViewCommand::OpenDate(selected)),
+            _ => (),
+        };
+        None
+    }
+}
+
+/// Colour for the time logged on a day relative to the target, days
+/// without any time are only coloured when `missed`, i.e. past and with
+/// nothing logged at all
+pub(crate) fn heat(minutes: i64, target_minutes: i64, missed: bool) -> Option<Color> {
+    if minutes <= 0 {
+        return match missed && target_minutes > 0 {
+            true => Some(MISSED),
+            false => None,
+        };
+    }
+    let ratio = minutes as f64 / target_minutes.max(1) as f64;
+    HEAT.iter()
+        .find(|(limit, _, _)| ratio < *limit)
+        .map(|(_, color, _)| *color)
+}
+
+fn legend<'a>(week_target: i64) -> Paragraph<'a> {
+    let mut spans = vec![Span::styled(
+        format!("target {} a week ", LogDuration::from_minutes(week_target)),
+        Style::default().fg(Color::DarkGray),
+    )];
+    for (_, color, label) in HEAT {
+        spans.push(Span::styled("■", Style::default().fg(*color)));
+        spans.push(Span::raw(format!(" {} ", label)));
+    }
+    spans.push(Span::styled("■", Style::default().fg(MISSED)));
+    spans.push(Span::raw(" missed "));
+    spans.push(Span::styled("■", Style::default().fg(DAY_OFF)));
+    spans.push(Span::raw(" day off"));
+    Paragraph::new(Spans::from(spans))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::time::FrozenTimeFactory;
+
+    use super::*;
+
+    #[test]
+    fn heat_relative_to_target() {
+        assert_eq!(None, heat(0, 480, false));
+        assert_eq!(Some(MISSED), heat(0, 480, true));
+        assert_eq!(None, heat(0, 0, true));
+        assert_eq!(Some(Color::Red), heat(239, 480, false));
+        assert_eq!(Some(Color::Yellow), heat(240, 480, false));
+        assert_eq!(Some(Color::Green), heat(432, 480, false));
+        assert_eq!(Some(Color::Green), heat(480, 480, true));
+        assert_eq!(Some(Color::Magenta), heat(528, 480, false));
+    }
+
+    #[test]
+    fn navigates_days_weeks_and_months() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let mut calendar = Calendar::new(&time, &config);
+
+        calendar.handle(&KeyName::FocusPrevious);
+        assert_eq!("2026-01-07", calendar.selected.to_string());
+        calendar.handle(&KeyName::SelectPrevious);
+        assert_eq!("2025-12-31", calendar.selected.to_string());
+        calendar.handle(&KeyName::PreviousPage);
+        assert_eq!("2025-11-30", calendar.selected.to_string());
+        calendar.handle(&KeyName::SelectFirst);
+        assert_eq!("2025-11-01", calendar.selected.to_string());
+        calendar.handle(&KeyName::SelectLast);
+        assert_eq!("2025-11-30", calendar.selected.to_string());
+    }
+
+    #[test]
+    fn cannot_navigate_past_current_month() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let mut calendar = Calendar::new(&time, &config);
+
+        calendar.handle(&KeyName::SelectNext);
+        assert_eq!("2026-01-15", calendar.selected.to_string());
+        calendar.handle(&KeyName::NextPage);
+        assert_eq!("2026-01-15", calendar.selected.to_string());
+    }
+
+    #[test]
+    fn opens_selected_date() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let mut calendar = Calendar::new(&time, &config);
+
+        match calendar.handle(&KeyName::Select) {
+            Some(ViewCommand::OpenDate(date)) => assert_eq!("2026-01-08", date.to_string()),
+            _ => panic!("Expected open date command"),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use anyhow::Error;
+use chrono::NaiveDate;
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear},
+    Frame,
+};
+use tui_textarea::TextArea;
+
+use crate::{
+    app::config::Key,
+    model::time::TimeFactory,
+    parser::date::{parse_date, parse_date_range},
+    ui::centered_rect_absolute,
+};
+
+#[derive(Clone, Copy)]
+pub enum PromptMode {
+    Date,
+    Range,
+}
+
+pub enum DateSelection {
+    Date(NaiveDate),
+    /// Start and (exclusive) end of a range
+    Range(NaiveDate, NaiveDate),
+}
+
+/// Prompt for a date to navigate to, e.g. `2026-01-31`, `-3d` or
+/// `last monday`, or for a range of dates
+pub struct DatePrompt<'a> {
+    textarea: TextArea<'a>,
+    pub visible: bool,
+    mode: PromptMode,
+    error: Option<String>,
+    time: &'a dyn TimeFactory,
+}
+
+impl DatePrompt<'_> {
+    pub fn new(time: &dyn TimeFactory) -> DatePrompt<'_> {
+        DatePrompt {
+            textarea: DatePrompt::empty_textarea(),
+            visible: false,
+            mode: PromptMode::Date,
+            error: None,
+            time,
+        }
+    }
+
+    fn empty_textarea<'a>() -> TextArea<'a> {
+        let mut textarea = TextArea::default();
+        textarea.set_cursor_line_style(Style::default());
+        textarea
+    }
+
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>) -> Result<(), Error> {
+        if !self.visible {
+            return Ok(());
+        }
+        let area = centered_rect_absolute(64, 3, f.size());
+
+        let title = match self.mode {
+            PromptMode::Date => "Go to date",
+            PromptMode::Range => "Date range",
+        };
+        let block = Block::default().borders(Borders::ALL);
+        self.textarea.set_block(match (&self.error, self.mode) {
+            (Some(error), _) => block
+                .title(format!("{}: {}", title, error))
+                .border_style(Style::default().fg(Color::Red)),
+            (None, PromptMode::Date) => block.title(format!(
+                "{} (YYYY-MM-DD, -3d, last monday, 2026-W05, today)",
+                title
+            )),
+            (None, PromptMode::Range) => block.title(format!("{} (<start>..<end>)", title)),
+        });
+        self.textarea
+            .set_style(Style::default().fg(Color::LightGreen));
+
+        f.render_widget(Clear, area);
+        f.render_widget(self.textarea.widget(), area);
+        Ok(())
+    }
+
+    pub(crate) fn show(&mut self, mode: PromptMode) {
+        self.visible = true;
+        self.mode = mode;
+        self.error = None;
+        self.textarea = DatePrompt::empty_textarea();
+    }
+
+    /// Returns the selection once a valid date or range has been entered
+    pub(crate) fn handle(&mut self, key: &Key) -> Option<DateSelection> {
+        match key.event.code {
+            KeyCode::Esc => self.visible = false,
+            KeyCode::Enter => match self.parse() {
+                Ok(selection) => {
+                    self.visible = false;
+                    return Some(selection);
+                }
+                Err(err) => self.error = Some(err.to_string()),
+            },
+            _ => {
+                self.error = None;
+                self.textarea.input(key.event);
+            }
+        }
+        None
+    }
+
+    fn parse(&self) -> Result<DateSelection, Error> {
+        let text = &self.textarea.lines()[0];
+        let today = self.time.now().date();
+        Ok(match self.mode {
+            PromptMode::Date => DateSelection::Date(parse_date(text, today)?),
+            PromptMode::Range => {
+                let (start, end) = parse_date_range(text, today)?;
+                DateSelection::Range(start, end)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{model::time::FrozenTimeFactory, ui::stream_input_to};
+
+    use super::*;
+
+    #[test]
+    fn returns_date_on_enter() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let mut prompt = DatePrompt::new(&time);
+        prompt.show(PromptMode::Date);
+        stream_input_to("-3d".to_string(), |key| {
+            prompt.handle(&key);
+        });
+        match prompt.handle(&Key::for_key_code(KeyCode::Enter)) {
+            Some(DateSelection::Date(date)) => assert_eq!("2026-01-05", date.to_string()),
+            _ => panic!("Expected a date"),
+        }
+        assert!(!prompt.visible);
+    }
+
+    #[test]
+    fn returns_range_on_enter() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let mut prompt = DatePrompt::new(&time);
+        prompt.show(PromptMode::Range);
+        stream_input_to("-1w..today".to_string(), |key| {
+            prompt.handle(&key);
+        });
+        match prompt.handle(&Key::for_key_code(KeyCode::Enter)) {
+            Some(DateSelection::Range(start, end)) => {
+                assert_eq!("2026-01-01", start.to_string());
+                assert_eq!("2026-01-09", end.to_string());
+            }
+            _ => panic!("Expected a range"),
+        }
+    }
+
+    #[test]
+    fn stays_visible_with_invalid_date() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let mut prompt = DatePrompt::new(&time);
+        prompt.show(PromptMode::Date);
+        stream_input_to("foo".to_string(), |key| {
+            prompt.handle(&key);
+        });
+        assert!(prompt.handle(&Key::for_key_code(KeyCode::Enter)).is_none());
+        assert!(prompt.visible);
+        assert!(prompt.error.is_some());
+    }
+}
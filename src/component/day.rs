@@ -1,4 +1,5 @@
 use anyhow::{Error, Result};
+use chrono::Duration;
 use tui::{
     backend::Backend,
     layout::{Constraint, Layout, Margin, Rect},
@@ -6,25 +7,69 @@ use tui::{
     Frame,
 };
 
-use crate::{app::config::KeyName, model::model::LogDays, parser::token::TokenKind};
+use crate::{
+    app::{
+        config::{Config, KeyName},
+        ViewCommand,
+    },
+    model::{model::LogDays, target::Schedule, time::TimeFactory},
+    parser::token::TokenKind,
+};
+
+use super::{
+    log_table::LogTable, progress, table_scroll::TableScroll, timeline::Timeline,
+    token_entries::TokenEntries, token_summary_table::TokenSummaryTable,
+};
+
+enum DayFocus {
+    Log,
+    Tags,
+    Tickets,
+}
+
+impl DayFocus {
+    fn next(&self) -> DayFocus {
+        match self {
+            DayFocus::Log => DayFocus::Tags,
+            DayFocus::Tags => DayFocus::Tickets,
+            DayFocus::Tickets => DayFocus::Log,
+        }
+    }
 
-use super::{log_table::LogTable, token_summary_table::TokenSummaryTable};
+    fn previous(&self) -> DayFocus {
+        match self {
+            DayFocus::Log => DayFocus::Tickets,
+            DayFocus::Tags => DayFocus::Log,
+            DayFocus::Tickets => DayFocus::Tags,
+        }
+    }
+}
 
 pub struct Day<'a> {
     pub index: usize,
+    time: &'a dyn TimeFactory,
+    config: &'a Config,
     pub log_table: LogTable,
     pub tag_summary: TokenSummaryTable<'a>,
     pub ticket_summary: TokenSummaryTable<'a>,
+    pub token_entries: TokenEntries,
+    timeline: Timeline<'a>,
+    focus: DayFocus,
     pub initialized: bool,
 }
 
 impl Day<'_> {
-    pub fn new<'a>() -> Day<'a> {
+    pub fn new<'a>(time: &'a dyn TimeFactory, config: &'a Config) -> Day<'a> {
         Day {
             index: 0,
-            log_table: LogTable {},
+            time,
+            config,
+            log_table: LogTable::new(),
             tag_summary: TokenSummaryTable::new("Tags"),
             ticket_summary: TokenSummaryTable::new("Tickets"),
+            token_entries: TokenEntries::new(),
+            timeline: Timeline::new(config),
+            focus: DayFocus::Log,
             initialized: false,
         }
     }
@@ -34,6 +79,7 @@ impl Day<'_> {
         f: &mut Frame<B>,
         area: Rect,
         log_days: &LogDays,
+        unfiltered: &LogDays,
     ) -> Result<(), Error> {
         // default to lastest entry
         if !self.initialized {
@@ -47,22 +93,49 @@ impl Day<'_> {
 
         let log_day = log_days.at(self.index);
 
-        let columns = Layout::default()
-            .direction(tui::layout::Direction::Horizontal)
-            .margin(0)
-            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        let rows = Layout::default()
+            .direction(tui::layout::Direction::Vertical)
+            .constraints([
+                Constraint::Length(Timeline::height(1)),
+                Constraint::Length(1),
+                Constraint::Min(4),
+            ])
             .split(area.inner(&Margin {
                 vertical: 2,
                 horizontal: 2,
             }));
+        self.timeline.draw(f, rows[0], &[log_day])?;
 
-        let container = Block::default().borders(Borders::ALL).title(format!(
+        let columns = Layout::default()
+            .direction(tui::layout::Direction::Horizontal)
+            .margin(0)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(rows[2]);
+
+        let date = log_day.date().naive_date();
+        let minutes = log_days.minutes_by_date();
+        let today = self.time.now().date();
+        let next = date + Duration::days(1);
+        let schedule = Schedule::new(self.config, log_days);
+        let mut prefix = format!(
             "{}/{} {}",
             self.index + 1,
             log_days.len(),
             log_day.date().to_verbose_string()
-        ));
-
+        );
+        if let Some(off) = schedule.day_off(date) {
+            prefix.push_str(&format!(" {}", off));
+        }
+        let container = Block::default()
+            .borders(Borders::ALL)
+            .title(progress::title(
+                prefix,
+                log_day.duration_total().num_minutes(),
+                &schedule.progress(&minutes, date, next, today),
+                schedule.balance(&unfiltered.minutes_by_date(), next, today),
+            ));
+
+        self.log_table.scroll.focused = matches!(self.focus, DayFocus::Log);
         self.log_table.draw(f, columns[0], log_day)?;
 
         let summary_rows = Layout::default()
@@ -70,10 +143,23 @@ impl Day<'_> {
             .constraints([Constraint::Percentage(50), Constraint::Min(2)])
             .split(columns[1]);
 
-        self.tag_summary
-            .draw(f, summary_rows[0], &log_day.tag_summary(TokenKind::Tag))?;
-        self.ticket_summary
-            .draw(f, summary_rows[1], &log_day.tag_summary(TokenKind::Ticket))?;
+        self.tag_summary.scroll.focused = matches!(self.focus, DayFocus::Tags);
+        self.ticket_summary.scroll.focused = matches!(self.focus, DayFocus::Tickets);
+        let allocation = log_days.allocation();
+        self.tag_summary.draw(
+            f,
+            summary_rows[0],
+            log_day.tag_summary(TokenKind::Tag, allocation),
+            None,
+            None,
+        )?;
+        self.ticket_summary.draw(
+            f,
+            summary_rows[1],
+            log_day.tag_summary(TokenKind::Ticket, allocation),
+            None,
+            None,
+        )?;
 
         f.render_widget(
             container,
@@ -83,9 +169,23 @@ impl Day<'_> {
             }),
         );
 
+        if let Some(token) = self.token_entries.token().cloned() {
+            let logs: Vec<_> = log_day
+                .logs_with_token(&token)
+                .into_iter()
+                .map(|log| (log_day.date(), log))
+                .collect();
+            self.token_entries.draw(f, &logs)?;
+        }
+
         Ok(())
     }
 
+    pub fn go_to(&mut self, index: usize) {
+        self.index = index;
+        self.initialized = true;
+    }
+
     fn next(&mut self) {
         self.index += 1
     }
@@ -96,11 +196,51 @@ impl Day<'_> {
         }
     }
 
-    pub(crate) fn handle(&mut self, key: &KeyName) {
+    pub(crate) fn handle(&mut self, key: &KeyName) -> Option<ViewCommand> {
+        if self.token_entries.visible() {
+            return self.token_entries.handle(key);
+        }
         match key {
             KeyName::PreviousPage => self.previous(),
             KeyName::NextPage => self.next(),
-            _ => (),
+            KeyName::CycleColour => {
+                self.timeline.handle(key);
+            }
+            KeyName::FocusNext => self.focus = self.focus.next(),
+            KeyName::FocusPrevious => self.focus = self.focus.previous(),
+            KeyName::CycleSort | KeyName::ReverseSort => {
+                let summary = match self.focus {
+                    DayFocus::Log => return None,
+                    DayFocus::Tags => &mut self.tag_summary,
+                    DayFocus::Tickets => &mut self.ticket_summary,
+                };
+                match key {
+                    KeyName::CycleSort => summary.cycle_sort(),
+                    _ => summary.reverse_sort(),
+                }
+            }
+            KeyName::Select => {
+                let token = match self.focus {
+                    DayFocus::Log => None,
+                    DayFocus::Tags => self.tag_summary.selected(),
+                    DayFocus::Tickets => self.ticket_summary.selected(),
+                };
+                if let Some(token) = token.cloned() {
+                    self.token_entries.show(token);
+                }
+            }
+            _ => {
+                self.focused_scroll().handle(key);
+            }
         };
+        None
+    }
+
+    fn focused_scroll(&mut self) -> &mut TableScroll {
+        match self.focus {
+            DayFocus::Log => &mut self.log_table.scroll,
+            DayFocus::Tags => &mut self.tag_summary.scroll,
+            DayFocus::Tickets => &mut self.ticket_summary.scroll,
+        }
     }
 }
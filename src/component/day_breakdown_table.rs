@@ -2,32 +2,72 @@ use anyhow::{Ok, Result};
 use tui::{
     backend::Backend,
     layout::{Constraint, Rect},
-    style::{Color, Style},
+    style::Style,
     text::Span,
     widgets::{Cell, Row, Table},
     Frame,
 };
 
-use crate::model::model::{LogDays, LogDuration};
+use crate::{
+    model::{
+        model::{LogDays, LogDuration},
+        target::Schedule,
+    },
+    parser::timesheet::DayOff,
+};
+
+use super::{progress, table_scroll::TableScroll};
 
-pub struct DayBreakdownTable {}
+pub struct DayBreakdownTable {
+    pub scroll: TableScroll,
+}
 
 impl DayBreakdownTable {
-    pub fn draw<B: Backend>(&self, f: &mut Frame<B>, area: Rect, log_days: &LogDays) -> Result<()> {
+    pub(crate) fn new() -> DayBreakdownTable {
+        DayBreakdownTable {
+            scroll: TableScroll::new(),
+        }
+    }
+
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        area: Rect,
+        log_days: &LogDays,
+        schedule: &Schedule,
+        time_off: &[(DayOff, usize, i64)],
+    ) -> Result<()> {
         let mut rows = vec![];
-        let binding = ["Day", "Hours"];
+        let binding = ["Day", "Hours", "Average"];
+        let header_style = self.scroll.header_style();
         let headers = binding
             .iter()
-            .map(|header| Cell::from(Span::styled(*header, Style::default().fg(Color::DarkGray))));
+            .map(|header| Cell::from(Span::styled(*header, header_style)));
 
-        for (day, minutes) in log_days.minutes_by_weekday() {
+        let averages = schedule.average_by_weekday(log_days);
+        for ((day, minutes), average) in log_days.minutes_by_weekday().into_iter().zip(averages) {
             let duration = LogDuration::from_minutes(minutes.try_into().unwrap());
             rows.push(Row::new([
                 Cell::from(day),
                 Cell::from(duration.to_string()),
+                Cell::from(
+                    average
+                        .map(|minutes| LogDuration::from_minutes(minutes).to_string())
+                        .unwrap_or_default(),
+                ),
+            ]));
+        }
+
+        // the hours of the days off, had they been worked
+        for (off, days, minutes) in time_off {
+            rows.push(Row::new([
+                Cell::from(progress::time_off_label(*off, *days)),
+                Cell::from(LogDuration::from_minutes(*minutes).to_string()),
+                Cell::default(),
             ]));
         }
 
+        self.scroll.update(rows.len(), area);
         let table = Table::new(rows)
             .header(
                 Row::new(headers)
@@ -35,8 +75,14 @@ impl DayBreakdownTable {
                     .bottom_margin(1)
                     .style(Style::default()),
             )
-            .widths(&[Constraint::Percentage(50), Constraint::Percentage(50)]);
-        f.render_widget(table, area);
+            .highlight_style(self.scroll.highlight_style())
+            .widths(&[
+                Constraint::Percentage(30),
+                Constraint::Percentage(35),
+                Constraint::Percentage(35),
+            ]);
+        f.render_stateful_widget(table, self.scroll.table_area(area), self.scroll.state());
+        self.scroll.draw_indicator(f, area);
         Ok(())
     }
 }
@@ -6,10 +6,13 @@ use tui::{
     widgets::{Block, Borders, Clear},
     Frame,
 };
-use tui_textarea::TextArea;
+use tui_textarea::{CursorMove, TextArea};
 
 use crate::{
-    app::config::{Config, Key as PtKey},
+    app::{
+        config::{Config, Key as PtKey},
+        history::FilterHistory,
+    },
     parser::filter::{parse_filter, Filter as ParserFilter},
     ui::centered_rect_absolute,
 };
@@ -19,7 +22,10 @@ pub struct Filter<'a> {
     pub visible: bool,
     pub valid: bool,
     pub filter: Option<ParserFilter>,
+    /// Incremented whenever the filter changes
+    pub revision: u64,
     pub original_filter: Vec<String>,
+    pub history: FilterHistory,
     pub config: &'a Config,
 }
 
@@ -32,8 +38,10 @@ impl Filter<'_> {
             visible: false,
             valid: false,
             filter: None,
+            revision: 0,
             config,
             original_filter: [].to_vec(),
+            history: FilterHistory::default(),
         }
     }
     pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>) -> Result<(), Error> {
@@ -45,8 +53,11 @@ impl Filter<'_> {
 
         self.textarea
             .set_block(Block::default().borders(Borders::ALL).title("Filter"));
-        self.textarea
-            .set_style(Style::default().fg(Color::LightGreen));
+        let colour = match self.valid {
+            true => Color::LightGreen,
+            false => Color::Red,
+        };
+        self.textarea.set_style(Style::default().fg(colour));
 
         f.render_widget(Clear, area);
         f.render_widget(self.textarea.widget(), area);
@@ -60,20 +71,52 @@ impl Filter<'_> {
 
         if key.event.code == KeyCode::Enter {
             self.visible = false;
+            match self.valid {
+                true => self.history.push(&self.textarea.lines()[0]),
+                false => self.history.reset(),
+            }
+            return;
+        }
+
+        if key.event.code == KeyCode::Up {
+            let current = self.textarea.lines()[0].to_string();
+            if let Some(previous) = self.history.older(&current).map(str::to_string) {
+                self.set_text(previous);
+            }
+            return;
+        }
+
+        if key.event.code == KeyCode::Down {
+            if let Some(next) = self.history.newer().map(str::to_string) {
+                self.set_text(next);
+            }
             return;
         }
 
         if key.event.code == KeyCode::Esc {
             self.visible = false;
+            self.history.reset();
             self.textarea = TextArea::new(self.original_filter.to_vec());
             self.textarea.set_cursor_line_style(Style::default());
         }
 
         self.textarea.input(key.event);
+        self.parse();
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.textarea = TextArea::new(vec![text]);
+        self.textarea.set_cursor_line_style(Style::default());
+        self.textarea.move_cursor(CursorMove::End);
+        self.parse();
+    }
+
+    fn parse(&mut self) {
         match parse_filter(&self.textarea.lines()[0], self.config) {
             Ok(ok) => {
                 self.valid = true;
                 self.filter = Some(ok);
+                self.revision += 1;
             }
             Err(_err) => {
                 self.valid = false;
@@ -81,6 +124,14 @@ impl Filter<'_> {
         }
     }
 
+    /// Replace the current filter, as if it had been entered by the user
+    pub(crate) fn apply(&mut self, text: &str) {
+        self.set_text(text.to_string());
+        if self.valid {
+            self.history.push(text);
+        }
+    }
+
     pub(crate) fn show(&mut self) {
         self.visible = true;
         self.original_filter = self.textarea.lines().to_vec().clone();
@@ -129,11 +180,50 @@ mod test {
                 name: "myproject".to_string(),
                 ticket_prefix: "PROJECT-".to_string(),
                 tags: vec![],
+                rate: None,
+                recipient: None,
             }],
+            ..Config::empty()
         };
         let mut filter = Filter::new(&config);
         filter.visible = true;
         stream_input_to("PROJECT-123".to_string(), |key| filter.handle(&key));
         assert_eq!("Ticket(PROJECT-123)", filter.filter.unwrap().to_string());
     }
+
+    #[test]
+    pub fn recalls_history_with_up_and_down() {
+        let binding = Config::empty();
+        let mut filter = Filter::new(&binding);
+        filter.history = FilterHistory::new(vec!["@foobar".to_string()]);
+        filter.show();
+        stream_input_to("@baz".to_string(), |key| filter.handle(&key));
+        filter.handle(&PtKey::for_key_code(KeyCode::Up));
+        assert_eq!("@foobar", filter.textarea.lines()[0]);
+        assert_eq!("Tag(foobar)", filter.filter.as_ref().unwrap().to_string());
+
+        filter.handle(&PtKey::for_key_code(KeyCode::Down));
+        assert_eq!("@baz", filter.textarea.lines()[0]);
+    }
+
+    #[test]
+    pub fn adds_applied_filter_to_history() {
+        let binding = Config::empty();
+        let mut filter = Filter::new(&binding);
+        filter.show();
+        stream_input_to("@foobar".to_string(), |key| filter.handle(&key));
+        filter.handle(&PtKey::for_key_code(KeyCode::Enter));
+        assert_eq!(vec!["@foobar"], filter.history.entries);
+    }
+
+    #[test]
+    pub fn leaves_invalid_filters_out_of_history() {
+        let binding = Config::empty();
+        let mut filter = Filter::new(&binding);
+        filter.show();
+        stream_input_to("@foobar (".to_string(), |key| filter.handle(&key));
+        assert!(!filter.valid);
+        filter.handle(&PtKey::for_key_code(KeyCode::Enter));
+        assert!(filter.history.entries.is_empty());
+    }
 }
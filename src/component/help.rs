@@ -0,0 +1,202 @@
+use anyhow::Ok;
+use tui::{
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    app::{
+        config::KeyName,
+        keymap::{KeyMap, Scope, BINDINGS},
+    },
+    ui::centered_rect_absolute,
+};
+
+const FILTER_GRAMMAR: &[(&str, &str)] = &[
+    ("@tag", "entries tagged with @tag"),
+    ("TICKET-1", "entries with a ticket of a configured project"),
+    ("NOT a", "entries not matching a"),
+    ("AND a b", "entries matching both a and b"),
+    ("OR a b", "entries matching either a or b"),
+];
+
+const FILTER_EXAMPLES: &[&str] = &[
+    "@pttlog",
+    "AND @pttlog JIRA-1234",
+    "OR AND @pttlog JIRA-1234 NOT @lunch",
+];
+
+/// Popup listing the key bindings of each view and the filter grammar
+pub struct Help {
+    pub visible: bool,
+    offset: u16,
+}
+
+impl Help {
+    pub(crate) fn new() -> Help {
+        Help {
+            visible: false,
+            offset: 0,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.offset = 0;
+    }
+
+    pub fn draw<B: tui::backend::Backend>(
+        &mut self,
+        f: &mut tui::Frame<B>,
+        keymap: &KeyMap,
+    ) -> anyhow::Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let lines = lines(keymap);
+        let area = centered_rect_absolute(
+            72.min(f.size().width.saturating_sub(4)),
+            (lines.len() as u16 + 2).min(f.size().height.saturating_sub(2)),
+            f.size(),
+        );
+        self.offset = self
+            .offset
+            .min((lines.len() as u16 + 2).saturating_sub(area.height));
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Help [j/k] scroll [esc] close"),
+            )
+            .scroll((self.offset, 0));
+
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+        Ok(())
+    }
+
+    pub(crate) fn handle(&mut self, key: &KeyName) {
+        match key {
+            KeyName::Close | KeyName::ToggleHelp | KeyName::Quit => self.toggle(),
+            KeyName::SelectNext => self.offset = self.offset.saturating_add(1),
+            KeyName::SelectPrevious => self.offset = self.offset.saturating_sub(1),
+            KeyName::SelectPageDown => self.offset = self.offset.saturating_add(10),
+            KeyName::SelectPageUp => self.offset = self.offset.saturating_sub(10),
+            KeyName::SelectFirst => self.offset = 0,
+            // clamped to the last page on draw
+            KeyName::SelectLast => self.offset = u16::MAX,
+            _ => (),
+        }
+    }
+}
+
+fn heading<'a>(text: String) -> Spans<'a> {
+    Spans::from(Span::styled(
+        text,
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+fn row<'a>(left: String, right: &str) -> Spans<'a> {
+    Spans::from(vec![
+        Span::styled(format!("  {:<22}", left), Style::default().fg(Color::Green)),
+        Span::raw(right.to_string()),
+    ])
+}
+
+/// Help text, generated from the bindings so that it reflects the
+/// configured keys
+fn lines<'a>(keymap: &KeyMap) -> Vec<Spans<'a>> {
+    let mut lines = vec![];
+    for scope in Scope::all() {
+        lines.push(heading(scope.to_string()));
+        for binding in BINDINGS
+            .iter()
+            .filter(|binding| binding.scopes.contains(&scope))
+        {
+            let chords = keymap
+                .chords(binding.action)
+                .iter()
+                .map(|chord| chord.to_string())
+                .collect::<Vec<_>>();
+            if chords.is_empty() {
+                continue;
+            }
+            lines.push(row(chords.join(", "), binding.description));
+        }
+        lines.push(Spans::default());
+    }
+
+    lines.push(heading("Filter".to_string()));
+    for (syntax, description) in FILTER_GRAMMAR {
+        lines.push(row(syntax.to_string(), description));
+    }
+    lines.push(Spans::from(Span::styled(
+        "  Criteria are prefix operators and can be nested, e.g.",
+        Style::default().fg(Color::DarkGray),
+    )));
+    for example in FILTER_EXAMPLES {
+        lines.push(Spans::from(Span::raw(format!("    {}", example))));
+    }
+    lines.push(Spans::from(Span::styled(
+        "  [up]/[down] recall previous filters, [enter] apply, [esc] cancel",
+        Style::default().fg(Color::DarkGray),
+    )));
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use crate::app::keymap::KeyChords;
+
+    use super::*;
+
+    fn text(keymap: &KeyMap) -> String {
+        lines(keymap)
+            .iter()
+            .map(|spans| {
+                spans
+                    .0
+                    .iter()
+                    .map(|span| span.content.to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn lists_every_binding() {
+        let text = text(&KeyMap::default());
+        for binding in BINDINGS {
+            assert!(
+                text.contains(binding.description),
+                "{} missing from help",
+                binding.name
+            );
+        }
+        assert!(text.contains("j, down"));
+    }
+
+    #[test]
+    fn shows_configured_chords() {
+        let keys = BTreeMap::from([("quit".to_string(), KeyChords::One("ctrl+c".to_string()))]);
+        let text = text(&KeyMap::new(&keys).unwrap());
+        assert!(text.contains("ctrl+c"));
+    }
+
+    #[test]
+    fn closes_on_close() {
+        let mut help = Help::new();
+        help.toggle();
+        help.handle(&KeyName::SelectNext);
+        help.handle(&KeyName::Close);
+        assert!(!help.visible);
+    }
+}
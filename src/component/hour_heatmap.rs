@@ -0,0 +1,96 @@
+use anyhow::{Ok, Result};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Span, Spans},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::model::model::{LogDays, LogDuration};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const SHADES: [&str; 5] = [" ", "░", "▒", "▓", "█"];
+const LABEL_WIDTH: u16 = 4;
+
+/// Minutes logged per hour of the day and weekday
+pub struct HourHeatmap {}
+
+impl HourHeatmap {
+    /// Header, one row per weekday and the legend
+    pub const HEIGHT: u16 = 9;
+
+    pub fn draw<B: Backend>(&self, f: &mut Frame<B>, area: Rect, log_days: &LogDays) -> Result<()> {
+        let buckets = log_days.minutes_by_weekday_hour();
+        let max = buckets.iter().flatten().copied().max().unwrap_or(0);
+        let cell_width = (area.width.saturating_sub(LABEL_WIDTH) / 24).clamp(1, 3) as usize;
+
+        let mut header = " ".repeat(LABEL_WIDTH as usize);
+        let step = match cell_width {
+            1 => 6,
+            2 => 2,
+            _ => 1,
+        };
+        for hour in (0..24).step_by(step) {
+            header.push_str(&format!("{:<width$}", hour, width = cell_width * step));
+        }
+        let mut lines = vec![Spans::from(Span::styled(
+            header,
+            Style::default().fg(Color::DarkGray),
+        ))];
+
+        for (weekday, hours) in WEEKDAYS.iter().zip(buckets.iter()) {
+            let mut spans = vec![Span::styled(
+                format!("{:<width$}", weekday, width = LABEL_WIDTH as usize),
+                Style::default().fg(Color::DarkGray),
+            )];
+            spans.extend(hours.iter().map(|minutes| {
+                Span::styled(
+                    shade(*minutes, max).repeat(cell_width),
+                    Style::default().fg(Color::Red),
+                )
+            }));
+            lines.push(Spans::from(spans));
+        }
+
+        let mut legend = vec![Span::raw(" ".repeat(LABEL_WIDTH as usize))];
+        for shade in SHADES.iter().skip(1) {
+            legend.push(Span::styled(*shade, Style::default().fg(Color::Red)));
+            legend.push(Span::raw(" "));
+        }
+        legend.push(Span::styled(
+            format!("up to {} per hour", LogDuration::from_minutes(max as i64)),
+            Style::default().fg(Color::DarkGray),
+        ));
+        lines.push(Spans::from(legend));
+
+        f.render_widget(Paragraph::new(lines), area);
+        Ok(())
+    }
+}
+
+/// Shade for the minutes relative to the busiest hour, any time at all is
+/// at least the lightest shade
+fn shade(minutes: u64, max: u64) -> &'static str {
+    if minutes == 0 || max == 0 {
+        return SHADES[0];
+    }
+    let levels = (SHADES.len() - 1) as u64;
+    SHADES[(minutes * levels).div_ceil(max) as usize]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shades_relative_to_max() {
+        assert_eq!(" ", shade(0, 120));
+        assert_eq!("░", shade(1, 120));
+        assert_eq!("░", shade(30, 120));
+        assert_eq!("▒", shade(31, 120));
+        assert_eq!("▓", shade(90, 120));
+        assert_eq!("█", shade(120, 120));
+    }
+}
@@ -1,18 +1,40 @@
 use std::fmt::Display;
 
 use anyhow::{Error, Result};
-use chrono::{Duration, Months, NaiveDate};
+use chrono::{Datelike, Duration, Months, NaiveDate};
 use tui::{
-    backend::Backend, layout::{Constraint, Layout, Margin, Rect}, style::{Color, Style}, text::{Span, Spans}, widgets::{Block, Borders, Tabs}, Frame
+    backend::Backend,
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Tabs},
+    Frame,
 };
 
 use crate::{
-    app::config::KeyName, component::line_item_table::LineItemTable, model::{model::LogDays, time::TimeFactory}, parser::token::TokenKind
+    app::{
+        config::{Config, KeyName},
+        ViewCommand,
+    },
+    component::line_item_table::LineItemTable,
+    model::{
+        model::{LogDays, TokenSeries},
+        rounding,
+        target::Schedule,
+        time::TimeFactory,
+    },
+    parser::token::TokenKind,
 };
 
 use super::{
-    day_breakdown_chart::DayBreakdownChart, day_breakdown_table::DayBreakdownTable,
-    token_summary_table::TokenSummaryTable,
+    day_breakdown_chart::DayBreakdownChart,
+    day_breakdown_table::DayBreakdownTable,
+    hour_heatmap::HourHeatmap,
+    progress,
+    table_scroll::TableScroll,
+    timeline::Timeline,
+    token_entries::TokenEntries,
+    token_summary_table::{TokenSummaryTable, TREND_PERIODS},
 };
 
 pub struct IntervalView<'a> {
@@ -21,12 +43,17 @@ pub struct IntervalView<'a> {
     date_start: NaiveDate,
     date_end: NaiveDate,
     time: &'a dyn TimeFactory,
+    config: &'a Config,
     tag_summary: TokenSummaryTable<'a>,
     ticket_summary: TokenSummaryTable<'a>,
     duration: ReportDuration,
     day_breakdown_chart: DayBreakdownChart,
+    hour_heatmap: HourHeatmap,
     day_breakdown_table: DayBreakdownTable,
     line_item_table: LineItemTable,
+    token_entries: TokenEntries,
+    timeline: Timeline<'a>,
+    focus: SummaryFocus,
 }
 
 #[derive(Clone, Copy)]
@@ -34,7 +61,21 @@ pub enum ReportDuration {
     Day,
     Week,
     Month,
+    /// Quarters of the fiscal year starting in the month `fiscal_year_start`
+    Quarter {
+        fiscal_year_start: u32,
+    },
     Year,
+    /// Periods of `days` starting on `start` or any multiple of `days` before
+    /// or after it
+    Sprint {
+        start: NaiveDate,
+        days: u32,
+    },
+    /// A range of `days` entered by the user
+    Custom {
+        days: u32,
+    },
 }
 
 impl Display for ReportDuration {
@@ -43,23 +84,51 @@ impl Display for ReportDuration {
             ReportDuration::Day => "day",
             ReportDuration::Week => "week",
             ReportDuration::Month => "month",
+            ReportDuration::Quarter { .. } => "quarter",
             ReportDuration::Year => "year",
+            ReportDuration::Sprint { .. } => "sprint",
+            ReportDuration::Custom { .. } => "range",
         })
     }
 }
 
-enum IntervalTab
-{
+enum IntervalTab {
     Summary,
-    List
+    List,
+    Timeline,
+}
+
+enum SummaryFocus {
+    Weekdays,
+    Tags,
+    Tickets,
+}
+
+impl SummaryFocus {
+    fn next(&self) -> SummaryFocus {
+        match self {
+            SummaryFocus::Weekdays => SummaryFocus::Tags,
+            SummaryFocus::Tags => SummaryFocus::Tickets,
+            SummaryFocus::Tickets => SummaryFocus::Weekdays,
+        }
+    }
+
+    fn previous(&self) -> SummaryFocus {
+        match self {
+            SummaryFocus::Weekdays => SummaryFocus::Tickets,
+            SummaryFocus::Tags => SummaryFocus::Weekdays,
+            SummaryFocus::Tickets => SummaryFocus::Tags,
+        }
+    }
 }
 
 impl IntervalView<'_> {
-    pub fn new(
-        time: &dyn TimeFactory,
+    pub fn new<'a>(
+        time: &'a dyn TimeFactory,
+        config: &'a Config,
         start_date: NaiveDate,
         duration: ReportDuration,
-    ) -> IntervalView<'_> {
+    ) -> IntervalView<'a> {
         IntervalView {
             initialized: false,
             duration,
@@ -67,11 +136,16 @@ impl IntervalView<'_> {
             date_start: start_date,
             date_end: shift_range(&duration, start_date, 1),
             time,
+            config,
             tag_summary: TokenSummaryTable::new("Tags"),
             ticket_summary: TokenSummaryTable::new("Tickets"),
             day_breakdown_chart: DayBreakdownChart {},
-            day_breakdown_table: DayBreakdownTable {},
-            line_item_table: LineItemTable {},
+            hour_heatmap: HourHeatmap {},
+            day_breakdown_table: DayBreakdownTable::new(),
+            line_item_table: LineItemTable::new(),
+            token_entries: TokenEntries::new(),
+            timeline: Timeline::new(config),
+            focus: SummaryFocus::Tags,
         }
     }
 
@@ -80,21 +154,50 @@ impl IntervalView<'_> {
         f: &mut Frame<B>,
         area: Rect,
         log_days: &LogDays,
+        unfiltered: &LogDays,
     ) -> Result<(), Error> {
         // default to lastest entry
         if !self.initialized {
             self.initialized = true;
         }
 
+        let boundaries = self.trend_boundaries();
+        let trends = (
+            log_days.token_series(TokenKind::Tag, &boundaries),
+            log_days.token_series(TokenKind::Ticket, &boundaries),
+        );
+        let minutes = log_days.minutes_by_date();
+        let today = self.time.now().date();
+        let schedule = Schedule::new(self.config, log_days);
+        let balance = self.balance(&schedule, unfiltered);
         let log_days = log_days.until(self.date_start, self.date_end);
 
-        let container = Block::default().borders(Borders::ALL).title(format!(
+        let name = match self.duration {
+            ReportDuration::Quarter { fiscal_year_start } => {
+                quarter_name(self.date_start, fiscal_year_start)
+            }
+            duration => duration.to_string(),
+        };
+        let mut prefix = format!(
             "{} from {} {} until {}",
-            self.duration,
+            name,
             self.date_start.format("%A"),
             self.date_start,
             self.date_end
-        ));
+        );
+        if let Some(days_off) =
+            progress::days_off(&schedule.days_off(self.date_start, self.date_end))
+        {
+            prefix.push_str(&format!(" ({})", days_off));
+        }
+        let container = Block::default()
+            .borders(Borders::ALL)
+            .title(progress::title(
+                prefix,
+                log_days.duration_total().num_minutes(),
+                &schedule.progress(&minutes, self.date_start, self.date_end, today),
+                balance,
+            ));
 
         f.render_widget(
             container,
@@ -105,14 +208,17 @@ impl IntervalView<'_> {
         );
 
         let tabs = Tabs::new(vec![
-            Spans::from(vec![Span::raw("Tab"), ]),
-            Spans::from(vec![Span::raw("Summary"), ]),
-            Spans::from(vec![Span::raw("List"), ]),
-        ]).highlight_style(Style::default().fg(Color::Green));
+            Spans::from(vec![Span::raw("Tab")]),
+            Spans::from(vec![Span::raw("Summary")]),
+            Spans::from(vec![Span::raw("List")]),
+            Spans::from(vec![Span::raw("Timeline")]),
+        ])
+        .highlight_style(Style::default().fg(Color::Green));
 
         let tabs = match self.tab {
             IntervalTab::Summary => tabs.select(1),
             IntervalTab::List => tabs.select(2),
+            IntervalTab::Timeline => tabs.select(3),
         };
         f.render_widget(
             tabs,
@@ -120,14 +226,28 @@ impl IntervalView<'_> {
                 x: area.x,
                 y: area.height,
                 width: area.width,
-                height: 1 
-            }
+                height: 1,
+            },
         );
 
         match self.tab {
-            IntervalTab::Summary => self.render_summary(f, area, &log_days),
-            IntervalTab::List => self.render_list(f, area, &log_days),
+            IntervalTab::Summary => self.render_summary(f, area, &log_days, &schedule, &trends)?,
+            IntervalTab::List => self.render_list(f, area, &log_days, &schedule)?,
+            IntervalTab::Timeline => self.timeline.draw(
+                f,
+                area.inner(&Margin {
+                    vertical: 2,
+                    horizontal: 2,
+                }),
+                &log_days.iter().collect::<Vec<_>>(),
+            )?,
+        };
+
+        if let Some(token) = self.token_entries.token().cloned() {
+            self.token_entries
+                .draw(f, &log_days.logs_with_token(&token))?;
         }
+        Ok(())
     }
 
     fn render_list<B: Backend>(
@@ -135,11 +255,18 @@ impl IntervalView<'_> {
         f: &mut Frame<B>,
         area: Rect,
         log_days: &LogDays,
+        schedule: &Schedule,
     ) -> Result<(), Error> {
+        self.line_item_table.scroll.focused = true;
         self.line_item_table.draw(
             f,
-            area.inner(&Margin { vertical: 2, horizontal: 2 }),
-            log_days
+            area.inner(&Margin {
+                vertical: 2,
+                horizontal: 2,
+            }),
+            log_days,
+            schedule,
+            self.config.rounding.as_ref(),
         )
     }
 
@@ -148,11 +275,13 @@ impl IntervalView<'_> {
         f: &mut Frame<B>,
         area: Rect,
         log_days: &LogDays,
+        schedule: &Schedule,
+        (tag_trends, ticket_trends): &(TokenSeries, TokenSeries),
     ) -> Result<(), Error> {
         let columns = Layout::default()
             .direction(tui::layout::Direction::Horizontal)
             .margin(0)
-            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
             .split(area.inner(&Margin {
                 vertical: 2,
                 horizontal: 2,
@@ -160,13 +289,25 @@ impl IntervalView<'_> {
 
         let left_rows = Layout::default()
             .direction(tui::layout::Direction::Vertical)
-            .constraints([Constraint::Percentage(50), Constraint::Min(2)])
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Length(HourHeatmap::HEIGHT + 1),
+                Constraint::Min(2),
+            ])
             .split(columns[0].inner(&Margin {
                 vertical: 2,
                 horizontal: 2,
             }));
         self.day_breakdown_chart.draw(f, left_rows[0], log_days)?;
-        self.day_breakdown_table.draw(f, left_rows[1], log_days)?;
+        self.hour_heatmap.draw(f, left_rows[1], log_days)?;
+        self.day_breakdown_table.scroll.focused = matches!(self.focus, SummaryFocus::Weekdays);
+        self.day_breakdown_table.draw(
+            f,
+            left_rows[2],
+            log_days,
+            schedule,
+            &schedule.time_off(self.date_start, self.date_end),
+        )?;
 
         let right_rows = Layout::default()
             .direction(tui::layout::Direction::Vertical)
@@ -176,28 +317,55 @@ impl IntervalView<'_> {
                 horizontal: 2,
             }));
 
-        self.tag_summary
-            .draw(f, right_rows[0], &log_days.tag_summary(TokenKind::Tag))?;
-        self.ticket_summary
-            .draw(f, right_rows[1], &log_days.tag_summary(TokenKind::Ticket))?;
+        self.tag_summary.scroll.focused = matches!(self.focus, SummaryFocus::Tags);
+        self.ticket_summary.scroll.focused = matches!(self.focus, SummaryFocus::Tickets);
+        let rounded = |kind| {
+            self.config
+                .rounding
+                .as_ref()
+                .map(|config| rounding::token_totals(config, log_days, kind))
+        };
+        let (tags_rounded, tickets_rounded) = (rounded(TokenKind::Tag), rounded(TokenKind::Ticket));
+        self.tag_summary.draw(
+            f,
+            right_rows[0],
+            log_days.tag_summary(TokenKind::Tag),
+            Some(tag_trends),
+            tags_rounded.as_ref(),
+        )?;
+        self.ticket_summary.draw(
+            f,
+            right_rows[1],
+            log_days.tag_summary(TokenKind::Ticket),
+            Some(ticket_trends),
+            tickets_rounded.as_ref(),
+        )?;
 
         Ok(())
     }
 
-    pub(crate) fn handle(&mut self, key: &KeyName) {
+    pub(crate) fn handle(&mut self, key: &KeyName) -> Option<ViewCommand> {
+        if self.token_entries.visible() {
+            return self.token_entries.handle(key);
+        }
         match key {
             KeyName::NextTab => {
                 self.tab = match self.tab {
-                    IntervalTab::List => IntervalTab::Summary,
                     IntervalTab::Summary => IntervalTab::List,
+                    IntervalTab::List => IntervalTab::Timeline,
+                    IntervalTab::Timeline => IntervalTab::Summary,
                 }
-            },
+            }
             KeyName::PrevTab => {
                 self.tab = match self.tab {
+                    IntervalTab::Summary => IntervalTab::Timeline,
                     IntervalTab::List => IntervalTab::Summary,
-                    IntervalTab::Summary => IntervalTab::List,
+                    IntervalTab::Timeline => IntervalTab::List,
                 }
-            },
+            }
+            KeyName::CycleColour => {
+                self.timeline.handle(key);
+            }
             KeyName::PreviousPage => {
                 self.date_start = shift_range(&self.duration, self.date_start, -1);
                 self.date_end = shift_range(&self.duration, self.date_end, -1);
@@ -205,40 +373,167 @@ impl IntervalView<'_> {
             KeyName::NextPage => {
                 let next_start_date = shift_range(&self.duration, self.date_start, 1);
                 if next_start_date > self.time.now().date() {
-                    return;
+                    return None;
                 }
                 self.date_start = next_start_date;
                 self.date_end = shift_range(&self.duration, self.date_end, 1);
             }
-            _ => (),
+            KeyName::FocusNext => self.focus = self.focus.next(),
+            KeyName::FocusPrevious => self.focus = self.focus.previous(),
+            KeyName::CycleSort | KeyName::ReverseSort => {
+                let summary = match (&self.tab, &self.focus) {
+                    (IntervalTab::Summary, SummaryFocus::Tags) => &mut self.tag_summary,
+                    (IntervalTab::Summary, SummaryFocus::Tickets) => &mut self.ticket_summary,
+                    _ => return None,
+                };
+                match key {
+                    KeyName::CycleSort => summary.cycle_sort(),
+                    _ => summary.reverse_sort(),
+                }
+            }
+            KeyName::Select => {
+                let token = match (&self.tab, &self.focus) {
+                    (IntervalTab::Summary, SummaryFocus::Tags) => self.tag_summary.selected(),
+                    (IntervalTab::Summary, SummaryFocus::Tickets) => self.ticket_summary.selected(),
+                    _ => None,
+                };
+                if let Some(token) = token.cloned() {
+                    self.token_entries.show(token);
+                }
+            }
+            _ => match self.tab {
+                IntervalTab::Timeline => {
+                    self.timeline.handle(key);
+                }
+                _ => {
+                    self.focused_scroll().handle(key);
+                }
+            },
+        };
+        None
+    }
+
+    /// Flextime balance at the end of the period. It is against the whole
+    /// schedule, so counts the days before any filter.
+    fn balance(&self, schedule: &Schedule, unfiltered: &LogDays) -> Option<i64> {
+        schedule.balance(
+            &unfiltered.minutes_by_date(),
+            self.date_end,
+            self.time.now().date(),
+        )
+    }
+
+    /// Start of each of the last periods up to the current one, followed by
+    /// the end of the current period
+    fn trend_boundaries(&self) -> Vec<NaiveDate> {
+        let mut boundaries: Vec<NaiveDate> = (0..TREND_PERIODS as i64)
+            .rev()
+            .map(|periods| shift_range(&self.duration, self.date_start, -periods))
+            .collect();
+        boundaries.push(self.date_end);
+        boundaries
+    }
+
+    /// Show the given range, `end` being exclusive
+    pub fn set_range(&mut self, start: NaiveDate, end: NaiveDate) {
+        self.duration = ReportDuration::Custom {
+            days: (end - start).num_days().max(1) as u32,
         };
+        self.date_start = start;
+        self.date_end = end;
+    }
+
+    /// Show the period containing the given date
+    pub fn go_to(&mut self, date: NaiveDate) {
+        let date = date.min(self.time.now().date());
+        self.date_start = period_start(&self.duration, date);
+        self.date_end = shift_range(&self.duration, self.date_start, 1);
+    }
+
+    fn focused_scroll(&mut self) -> &mut TableScroll {
+        match (&self.tab, &self.focus) {
+            (IntervalTab::List | IntervalTab::Timeline, _) => &mut self.line_item_table.scroll,
+            (IntervalTab::Summary, SummaryFocus::Weekdays) => &mut self.day_breakdown_table.scroll,
+            (IntervalTab::Summary, SummaryFocus::Tags) => &mut self.tag_summary.scroll,
+            (IntervalTab::Summary, SummaryFocus::Tickets) => &mut self.ticket_summary.scroll,
+        }
     }
 }
+fn shift_months(date: NaiveDate, amount: i64) -> NaiveDate {
+    match amount < 0 {
+        true => date
+            .checked_sub_months(Months::new((-amount).try_into().unwrap()))
+            .unwrap(),
+        false => date
+            .checked_add_months(Months::new(amount.try_into().unwrap()))
+            .unwrap(),
+    }
+}
+
+fn period_start(duration: &ReportDuration, date: NaiveDate) -> NaiveDate {
+    match duration {
+        ReportDuration::Day => date,
+        ReportDuration::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        ReportDuration::Month => date.with_day(1).unwrap(),
+        ReportDuration::Quarter { fiscal_year_start } => {
+            let month = fiscal_month0(date, *fiscal_year_start);
+            shift_months(date.with_day(1).unwrap(), -((month % 3) as i64))
+        }
+        ReportDuration::Year => date.with_ordinal(1).unwrap(),
+        ReportDuration::Sprint { start, days } => {
+            let days = (*days).max(1) as i64;
+            *start + Duration::days((date - *start).num_days().div_euclid(days) * days)
+        }
+        ReportDuration::Custom { .. } => date,
+    }
+}
+
+/// Months from the start of the fiscal year to the date's month, 0 to 11
+fn fiscal_month0(date: NaiveDate, fiscal_year_start: u32) -> u32 {
+    (date.month0() + 13 - fiscal_year_start.clamp(1, 12)) % 12
+}
+
+/// E.g. "Q1 2026", or "Q1 FY2026/27" for a fiscal year not starting in
+/// January
+fn quarter_name(start: NaiveDate, fiscal_year_start: u32) -> String {
+    let month = fiscal_month0(start, fiscal_year_start);
+    let year = shift_months(start, -(month as i64)).year();
+    match fiscal_year_start.clamp(1, 12) {
+        1 => format!("Q{} {}", month / 3 + 1, year),
+        _ => format!("Q{} FY{}/{:02}", month / 3 + 1, year, (year + 1) % 100),
+    }
+}
+
 fn shift_range(duratinon: &ReportDuration, date: NaiveDate, amount: i64) -> NaiveDate {
     match duratinon {
         ReportDuration::Day => date + Duration::days(amount),
         ReportDuration::Week => date + Duration::weeks(amount),
-        ReportDuration::Month => match amount < 0 {
-            true => date.checked_sub_months(Months::new((-amount).try_into().unwrap())).unwrap(),
-            false => date.checked_add_months(Months::new(amount.try_into().unwrap())).unwrap(),
+        ReportDuration::Month => shift_months(date, amount),
+        ReportDuration::Quarter { .. } => shift_months(date, amount * 3),
+        ReportDuration::Year => shift_months(date, amount * 12),
+        ReportDuration::Sprint { days, .. } | ReportDuration::Custom { days } => {
+            date + Duration::days(amount * *days as i64)
         }
-        ReportDuration::Year => date + Duration::days(amount * 365),
     }
-
 }
 
-
 #[cfg(test)]
 mod test {
-    use crate::model::time::FrozenTimeFactory;
+    use crate::{
+        app::config::Targets,
+        model::time::FrozenTimeFactory,
+        parser::{filter::parse_filter, timesheet::parse_entry},
+    };
 
     use super::*;
 
     #[test]
     fn next_and_previous_pages() {
         let time = FrozenTimeFactory::new(2023, 1, 1, 12, 1);
+        let config = Config::empty();
         let mut view = IntervalView::new(
             &time,
+            &config,
             FrozenTimeFactory::new(2022, 1, 1, 12, 1).now().date(),
             ReportDuration::Week,
         );
@@ -255,8 +550,10 @@ mod test {
     #[test]
     fn cannot_advance_further_than_current_time() {
         let time = FrozenTimeFactory::new(2022, 1, 1, 12, 1);
+        let config = Config::empty();
         let mut view = IntervalView::new(
             &time,
+            &config,
             FrozenTimeFactory::new(2022, 1, 1, 12, 1).now().date(),
             ReportDuration::Week,
         );
@@ -269,4 +566,187 @@ mod test {
         view.handle(&KeyName::PreviousPage);
         assert_eq!("2021-12-25", view.date_start.to_string());
     }
+
+    #[test]
+    fn goes_to_period_containing_date() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let mut view = IntervalView::new(&time, &config, time.now().date(), ReportDuration::Week);
+        view.go_to(NaiveDate::from_ymd(2025, 3, 13));
+        assert_eq!("2025-03-10", view.date_start.to_string());
+        assert_eq!("2025-03-17", view.date_end.to_string());
+
+        let mut view = IntervalView::new(&time, &config, time.now().date(), ReportDuration::Month);
+        view.go_to(NaiveDate::from_ymd(2025, 3, 13));
+        assert_eq!("2025-03-01", view.date_start.to_string());
+        assert_eq!("2025-04-01", view.date_end.to_string());
+
+        // does not go beyond the current period
+        view.go_to(NaiveDate::from_ymd(2027, 3, 13));
+        assert_eq!("2026-01-01", view.date_start.to_string());
+    }
+
+    #[test]
+    fn trend_boundaries_end_with_current_period() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let view = IntervalView::new(
+            &time,
+            &config,
+            NaiveDate::from_ymd(2026, 1, 1),
+            ReportDuration::Month,
+        );
+        let boundaries = view.trend_boundaries();
+        assert_eq!(TREND_PERIODS + 1, boundaries.len());
+        assert_eq!("2025-06-01", boundaries[0].to_string());
+        assert_eq!("2026-01-01", boundaries[TREND_PERIODS - 1].to_string());
+        assert_eq!("2026-02-01", boundaries[TREND_PERIODS].to_string());
+    }
+
+    #[test]
+    fn year_trend_boundaries_across_leap_years() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let mut view = IntervalView::new(&time, &config, time.now().date(), ReportDuration::Year);
+        view.go_to(NaiveDate::from_ymd(2025, 3, 1));
+        let boundaries = view.trend_boundaries();
+        assert!(boundaries.iter().all(|boundary| boundary.ordinal() == 1));
+        assert_eq!(
+            (2025 - TREND_PERIODS as i32 + 1..=2026).collect::<Vec<i32>>(),
+            boundaries
+                .iter()
+                .map(|boundary| boundary.year())
+                .collect::<Vec<i32>>()
+        );
+    }
+
+    #[test]
+    fn balance_ignores_the_filter() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config {
+            daily_target: 8.0,
+            targets: Targets {
+                start: Some(NaiveDate::from_ymd(2026, 1, 5)),
+                hours: None,
+            },
+            ..Config::empty()
+        };
+        let timesheet = "2026-01-05\n\
+            09:00-13:00 @acme\n\
+            13:00-17:00 @other\n";
+        let entries = parse_entry(timesheet, &config).unwrap().1.entries;
+        let log_days = LogDays::from_entries(time.now(), entries);
+        let filtered = log_days.filter(&parse_filter("@acme", &config).unwrap());
+        let view = IntervalView::new(
+            &time,
+            &config,
+            NaiveDate::from_ymd(2026, 1, 5),
+            ReportDuration::Day,
+        );
+        let schedule = Schedule::new(&config, &filtered);
+        assert_eq!(Some(0), view.balance(&schedule, &log_days));
+    }
+
+    #[test]
+    fn quarters() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let quarter = ReportDuration::Quarter {
+            fiscal_year_start: 1,
+        };
+        let mut view = IntervalView::new(&time, &config, time.now().date(), quarter);
+        view.go_to(NaiveDate::from_ymd(2025, 8, 13));
+        assert_eq!("2025-07-01", view.date_start.to_string());
+        assert_eq!("2025-10-01", view.date_end.to_string());
+
+        view.handle(&KeyName::NextPage);
+        assert_eq!("2025-10-01", view.date_start.to_string());
+        assert_eq!("2026-01-01", view.date_end.to_string());
+    }
+
+    #[test]
+    fn fiscal_quarters() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let quarter = ReportDuration::Quarter {
+            fiscal_year_start: 4,
+        };
+        let mut view = IntervalView::new(&time, &config, time.now().date(), quarter);
+        view.go_to(NaiveDate::from_ymd(2025, 12, 24));
+        assert_eq!("2025-10-01", view.date_start.to_string());
+        assert_eq!("Q3 FY2025/26", quarter_name(view.date_start, 4));
+
+        view.handle(&KeyName::NextPage);
+        assert_eq!("2026-01-01", view.date_start.to_string());
+        assert_eq!("2026-04-01", view.date_end.to_string());
+        assert_eq!("Q4 FY2025/26", quarter_name(view.date_start, 4));
+
+        view.go_to(NaiveDate::from_ymd(2025, 4, 1));
+        assert_eq!("2025-04-01", view.date_start.to_string());
+        assert_eq!("Q1 FY2025/26", quarter_name(view.date_start, 4));
+
+        view.handle(&KeyName::PreviousPage);
+        assert_eq!("2025-01-01", view.date_start.to_string());
+        assert_eq!("Q4 FY2024/25", quarter_name(view.date_start, 4));
+
+        let quarter = ReportDuration::Quarter {
+            fiscal_year_start: 2,
+        };
+        let start = |date| period_start(&quarter, date).to_string();
+        assert_eq!("2026-02-01", start(NaiveDate::from_ymd(2026, 2, 1)));
+        assert_eq!("2025-11-01", start(NaiveDate::from_ymd(2026, 1, 31)));
+        assert_eq!("Q2 2026", quarter_name(NaiveDate::from_ymd(2026, 5, 1), 1));
+    }
+
+    #[test]
+    fn years_across_a_leap_year() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let mut view = IntervalView::new(&time, &config, time.now().date(), ReportDuration::Year);
+        view.go_to(NaiveDate::from_ymd(2024, 6, 1));
+        assert_eq!("2024-01-01", view.date_start.to_string());
+        assert_eq!("2025-01-01", view.date_end.to_string());
+
+        view.handle(&KeyName::NextPage);
+        assert_eq!("2025-01-01", view.date_start.to_string());
+        assert_eq!("2026-01-01", view.date_end.to_string());
+
+        view.handle(&KeyName::PreviousPage);
+        view.handle(&KeyName::PreviousPage);
+        assert_eq!("2023-01-01", view.date_start.to_string());
+        assert_eq!("2024-01-01", view.date_end.to_string());
+    }
+
+    #[test]
+    fn sprints_are_anchored_on_start() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let duration = ReportDuration::Sprint {
+            start: NaiveDate::from_ymd(2026, 1, 5),
+            days: 14,
+        };
+        let mut view = IntervalView::new(&time, &config, time.now().date(), duration);
+        view.go_to(NaiveDate::from_ymd(2025, 12, 24));
+        assert_eq!("2025-12-22", view.date_start.to_string());
+        assert_eq!("2026-01-05", view.date_end.to_string());
+
+        view.handle(&KeyName::NextPage);
+        assert_eq!("2026-01-05", view.date_start.to_string());
+        assert_eq!("2026-01-19", view.date_end.to_string());
+    }
+
+    #[test]
+    fn custom_range_pages_by_its_length() {
+        let time = FrozenTimeFactory::new(2026, 1, 8, 12, 0);
+        let config = Config::empty();
+        let mut view = IntervalView::new(&time, &config, time.now().date(), ReportDuration::Day);
+        view.set_range(
+            NaiveDate::from_ymd(2025, 12, 1),
+            NaiveDate::from_ymd(2025, 12, 11),
+        );
+
+        view.handle(&KeyName::PreviousPage);
+        assert_eq!("2025-11-21", view.date_start.to_string());
+        assert_eq!("2025-12-01", view.date_end.to_string());
+    }
 }
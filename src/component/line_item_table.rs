@@ -7,29 +7,51 @@ use tui::{
 };
 
 use crate::{
-    model::model::LogDays,
+    app::config::Rounding,
+    model::{model::LogDays, rounding, target::Schedule},
     parser::{
         timesheet::Tokens,
         token::{Token, TokenKind},
     },
 };
 
-pub struct LineItemTable {}
+use super::table_scroll::TableScroll;
+
+pub struct LineItemTable {
+    pub scroll: TableScroll,
+}
 
 impl LineItemTable {
+    pub(crate) fn new() -> LineItemTable {
+        LineItemTable {
+            scroll: TableScroll::new(),
+        }
+    }
+
     pub fn draw<B: tui::backend::Backend>(
-        &self,
+        &mut self,
         f: &mut tui::Frame<B>,
         area: tui::layout::Rect,
         days: &LogDays,
+        schedule: &Schedule,
+        rounding: Option<&Rounding>,
     ) -> anyhow::Result<()> {
+        let side_by_side = rounding.is_some_and(|rounding| rounding.side_by_side);
         let mut rows = vec![];
+        let header_style = self.scroll.header_style();
         let headers = ["Date", "Description", "Duration"]
             .iter()
-            .map(|header| Cell::from(Span::styled(*header, Style::default().fg(Color::DarkGray))));
+            .map(|header| Cell::from(Span::styled(*header, header_style)));
         let _duration_total = days.duration_total();
 
         for day in days.iter() {
+            let mut description = description_non_ref(day.description().clone());
+            if let Some(off) = schedule.day_off(day.date().naive_date()) {
+                description.0.insert(
+                    0,
+                    Span::styled(format!("{} ", off), Style::default().fg(Color::Blue)),
+                );
+            }
             rows.push(Row::new([
                 Cell::from(
                     Spans::from(vec![
@@ -37,12 +59,12 @@ impl LineItemTable {
                         Span::styled("-", Style::default().fg(Color::DarkGray)),
                     ])
                 ),
-                Cell::from(description_non_ref(day.description().clone())),
-                Cell::from(
-                    Spans::from(vec![
-                        Span::raw(day.duration_total().to_string()),
-                    ])
-                ),
+                Cell::from(description),
+                Cell::from(Spans::from(vec![Span::raw(rounding::format(
+                    day.duration_total().num_minutes(),
+                    rounding.and_then(|rounding| rounding::day_total(rounding, day)),
+                    side_by_side,
+                ))])),
             ]));
         }
         rows.push(Row::new([
@@ -53,10 +75,15 @@ impl LineItemTable {
         rows.push(Row::new([
             Cell::from(Span::styled("Total:", Style::default().fg(Color::DarkGray))),
             Cell::default(),
-            Cell::from(Span::raw(days.duration_total().to_string())),
+            Cell::from(Span::raw(rounding::format(
+                days.duration_total().num_minutes(),
+                rounding.map(|rounding| rounding::total(rounding, days)),
+                side_by_side,
+            ))),
         ]));
 
-        f.render_widget(
+        self.scroll.update(days.len(), area);
+        f.render_stateful_widget(
             Table::new(rows)
                 .header(
                     Row::new(headers)
@@ -64,19 +91,25 @@ impl LineItemTable {
                         .bottom_margin(1)
                         .style(Style::default()),
                 )
+                .highlight_style(self.scroll.highlight_style())
                 .widths(&[
                     Constraint::Length(11),
                     Constraint::Percentage(65),
-                    Constraint::Length(11),
+                    Constraint::Length(match side_by_side {
+                        true => 19,
+                        false => 11,
+                    }),
                 ]),
-            area,
+            self.scroll.table_area(area),
+            self.scroll.state(),
         );
+        self.scroll.draw_indicator(f, area);
         Ok(())
     }
 }
 
 fn description_non_ref<'a>(tokens: Tokens) -> Spans<'a> {
-    let foo: Vec<Span> = tokens
+    let spans: Vec<Span> = tokens
         .to_vec()
         .iter()
         .map(|t: &Token| match t.kind {
@@ -91,24 +124,5 @@ fn description_non_ref<'a>(tokens: Tokens) -> Spans<'a> {
             ),
         })
         .collect();
-    Spans::from(foo)
+    Spans::from(spans)
 }
-fn description(tokens: &Tokens) -> Spans {
-    let foo = tokens
-        .to_vec()
-        .iter()
-        .map(|t: &Token| match t.kind {
-            TokenKind::Tag => Span::styled(
-                format!("@{}", t.to_string().to_owned()),
-                Style::default().fg(Color::Green),
-            ),
-            TokenKind::Prose => Span::raw(t.to_string().to_owned()),
-            TokenKind::Ticket => Span::styled(
-                t.to_string().to_owned().to_string(),
-                Style::default().fg(Color::Cyan),
-            ),
-        })
-        .collect::<Vec<_>>();
-    Spans::from(foo)
-}
-
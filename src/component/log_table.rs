@@ -1,5 +1,5 @@
 use anyhow::Ok;
-use chrono::{Local, Timelike};
+use chrono::{Local, NaiveDateTime, Timelike};
 use tui::{
     layout::Constraint,
     style::{Color, Style},
@@ -8,71 +8,44 @@ use tui::{
 };
 
 use crate::{
-    model::model::{LogDay, TimeRangeView},
+    model::model::{LogDay, LogEntry, TimeRangeView},
     parser::{
         timesheet::Tokens,
         token::{Token, TokenKind},
     },
 };
 
-pub struct LogTable {}
+use super::table_scroll::TableScroll;
+
+pub struct LogTable {
+    pub scroll: TableScroll,
+}
 
 impl LogTable {
+    pub(crate) fn new() -> LogTable {
+        LogTable {
+            scroll: TableScroll::new(),
+        }
+    }
+
     pub fn draw<B: tui::backend::Backend>(
-        &self,
+        &mut self,
         f: &mut tui::Frame<B>,
         area: tui::layout::Rect,
         log_day: &LogDay,
     ) -> anyhow::Result<()> {
         let mut rows = vec![];
+        let header_style = self.scroll.header_style();
         let headers = ["Time", "Duration", "Description", ""]
             .iter()
-            .map(|header| Cell::from(Span::styled(*header, Style::default().fg(Color::DarkGray))));
+            .map(|header| Cell::from(Span::styled(*header, header_style)));
         let _duration_total = log_day.duration_total();
         let now = Local::now().naive_local();
 
         for log in log_day.iter() {
             rows.push(Row::new([
-                Cell::from((|time_range: &TimeRangeView| {
-                    // 1. if today and end time not set show "now"
-                    Spans::from(vec![
-                        Span::raw(time_range.start.format("%H:%M").to_string()),
-                        Span::styled("-", Style::default().fg(Color::DarkGray)),
-                        (|| {
-                            if time_range.ongoing {
-                                return Span::styled(
-                                    now.format("%H:%M").to_string(),
-                                    Style::default()
-                                        .fg({
-                                            if now.num_seconds_from_midnight().is_multiple_of(2) {
-                                                Color::Black
-                                            } else {
-                                                Color::Gray
-                                            }
-                                        })
-                                        .bg(Color::DarkGray),
-                                );
-                            }
-                            Span::styled(
-                                time_range.end.format("%H:%M").to_string(),
-                                Style::default().fg(Color::DarkGray),
-                            )
-                        })(),
-                        Span::raw(""),
-                    ])
-                })(log.time_range())),
-                Cell::from((|range: &TimeRangeView| -> Spans {
-                    Spans::from(vec![
-                        Span::raw(range.duration().to_string()),
-                        Span::styled(
-                            format!(
-                                " {:.2}%",
-                                log.percentage_of_day(log_day.duration_total().num_minutes())
-                            ),
-                            Style::default().fg(Color::DarkGray),
-                        ),
-                    ])
-                })(log.time_range())),
+                Cell::from(time_range(log.time_range(), &now)),
+                Cell::from(duration(log, log_day)),
                 Cell::from(description(log.description())),
             ]));
         }
@@ -88,7 +61,8 @@ impl LogTable {
             Cell::default(),
         ]));
 
-        f.render_widget(
+        self.scroll.update(log_day.logs().len(), area);
+        f.render_stateful_widget(
             Table::new(rows)
                 .header(
                     Row::new(headers)
@@ -96,19 +70,63 @@ impl LogTable {
                         .bottom_margin(1)
                         .style(Style::default()),
                 )
+                .highlight_style(self.scroll.highlight_style())
                 .widths(&[
                     Constraint::Length(11),
                     Constraint::Length(11),
                     Constraint::Percentage(65),
                 ]),
-            area,
+            self.scroll.table_area(area),
+            self.scroll.state(),
         );
+        self.scroll.draw_indicator(f, area);
         Ok(())
     }
 }
 
-fn description(tokens: &Tokens) -> Spans {
-    let foo = tokens
+fn time_range<'a>(time_range: &TimeRangeView, now: &NaiveDateTime) -> Spans<'a> {
+    // 1. if today and end time not set show "now"
+    Spans::from(vec![
+        Span::raw(time_range.start.format("%H:%M").to_string()),
+        Span::styled("-", Style::default().fg(Color::DarkGray)),
+        if time_range.ongoing {
+            Span::styled(
+                now.format("%H:%M").to_string(),
+                Style::default()
+                    .fg({
+                        if now.num_seconds_from_midnight().is_multiple_of(2) {
+                            Color::Black
+                        } else {
+                            Color::Gray
+                        }
+                    })
+                    .bg(Color::DarkGray),
+            )
+        } else {
+            Span::styled(
+                time_range.end.format("%H:%M").to_string(),
+                Style::default().fg(Color::DarkGray),
+            )
+        },
+        Span::raw(""),
+    ])
+}
+
+fn duration<'a>(log: &LogEntry, log_day: &LogDay) -> Spans<'a> {
+    Spans::from(vec![
+        Span::raw(log.time_range().duration().to_string()),
+        Span::styled(
+            format!(
+                " {:.2}%",
+                log.percentage_of_day(log_day.duration_total().num_minutes())
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+pub(crate) fn description(tokens: &Tokens) -> Spans<'_> {
+    let spans = tokens
         .to_vec()
         .iter()
         .map(|t: &Token| match t.kind {
@@ -123,5 +141,5 @@ fn description(tokens: &Tokens) -> Spans {
             ),
         })
         .collect::<Vec<_>>();
-    Spans::from(foo)
+    Spans::from(spans)
 }
@@ -1,9 +1,17 @@
+pub mod calendar;
+pub mod date_prompt;
 pub mod day;
 pub mod day_breakdown_chart;
 pub mod day_breakdown_table;
 pub mod filter;
+pub mod help;
+pub mod hour_heatmap;
 pub mod interval_view;
 pub mod log_table;
+pub mod progress;
+pub mod token_entries;
 pub mod token_summary_table;
 pub mod status;
+pub mod table_scroll;
+pub mod timeline;
 pub mod line_item_table;
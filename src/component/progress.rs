@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use tui::{
+    style::{Color, Style},
+    text::{Span, Spans},
+};
+
+use crate::{
+    model::{model::LogDuration, target::Progress},
+    parser::timesheet::DayOff,
+};
+
+/// Container title for time logged against the target, e.g.
+/// `6h10m / 8h0m -1h50m balance +3h20m`
+pub fn title<'a>(
+    prefix: String,
+    logged: i64,
+    progress: &Progress,
+    balance: Option<i64>,
+) -> Spans<'a> {
+    let mut spans = vec![
+        Span::raw(format!(
+            "{} {} / {} ",
+            prefix,
+            LogDuration::from_minutes(logged),
+            LogDuration::from_minutes(progress.target)
+        )),
+        delta(progress.over_under()),
+    ];
+    if let Some(balance) = balance {
+        spans.push(Span::raw(" balance "));
+        spans.push(delta(balance));
+    }
+    Spans::from(spans)
+}
+
+/// Days off in a period, e.g. `1d holiday, 3d leave`
+pub fn days_off(days_off: &HashMap<DayOff, usize>) -> Option<String> {
+    let parts: Vec<String> = [DayOff::Holiday, DayOff::Leave]
+        .iter()
+        .filter_map(|off| days_off.get(off).map(|days| format!("{}d {}", days, off)))
+        .collect();
+    match parts.is_empty() {
+        true => None,
+        false => Some(parts.join(", ")),
+    }
+}
+
+/// Kind of day off with the number of days, e.g. `Leave (3d)`
+pub fn time_off_label(off: DayOff, days: usize) -> String {
+    let name = match off {
+        DayOff::Holiday => "Holiday",
+        DayOff::Leave => "Leave",
+    };
+    format!("{} ({}d)", name, days)
+}
+
+/// Signed duration, green when over and red when under
+pub fn delta<'a>(minutes: i64) -> Span<'a> {
+    let duration = LogDuration::from_minutes(minutes.abs());
+    match minutes {
+        0 => Span::styled(duration.to_string(), Style::default().fg(Color::DarkGray)),
+        _ if minutes > 0 => {
+            Span::styled(format!("+{}", duration), Style::default().fg(Color::Green))
+        }
+        _ => Span::styled(format!("-{}", duration), Style::default().fg(Color::Red)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn delta_is_signed() {
+        assert_eq!("+2h15m", delta(135).content);
+        assert_eq!("-45m", delta(-45).content);
+        assert_eq!("0m", delta(0).content);
+    }
+
+    #[test]
+    fn summarises_days_off() {
+        assert_eq!(None, days_off(&HashMap::new()));
+        assert_eq!(
+            Some("1d holiday, 3d leave".to_string()),
+            days_off(&HashMap::from([(DayOff::Leave, 3), (DayOff::Holiday, 1)]))
+        );
+    }
+
+    #[test]
+    fn title_with_balance() {
+        let progress = Progress {
+            logged: 370,
+            target: 480,
+        };
+        let text: String = title("Monday".to_string(), 370, &progress, Some(200))
+            .0
+            .iter()
+            .map(|span| span.content.to_string())
+            .collect();
+        assert_eq!("Monday 6h10m / 8h0m -1h50m balance +3h20m", text);
+    }
+}
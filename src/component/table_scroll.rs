@@ -0,0 +1,191 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Paragraph, TableState},
+    Frame,
+};
+
+use crate::app::config::KeyName;
+
+/// Selection and scroll position of a table, shared by the table components.
+///
+/// Only the first `len` rows of the table (i.e. not the totals) can be
+/// selected.
+pub struct TableScroll {
+    pub focused: bool,
+    state: TableState,
+    len: usize,
+    page_size: usize,
+}
+
+// header row and its bottom margin
+const HEADER_HEIGHT: u16 = 2;
+
+impl TableScroll {
+    pub(crate) fn new() -> TableScroll {
+        TableScroll {
+            focused: false,
+            state: TableState::default(),
+            len: 0,
+            page_size: 1,
+        }
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    /// Clamp the selection to the number of rows, called on each draw
+    pub fn update(&mut self, len: usize, area: Rect) {
+        self.len = len;
+        self.page_size = area.height.saturating_sub(HEADER_HEIGHT).max(1) as usize;
+        self.state.select(match len {
+            0 => None,
+            _ => Some(self.state.selected().unwrap_or(0).min(len - 1)),
+        });
+    }
+
+    pub fn state(&mut self) -> &mut TableState {
+        &mut self.state
+    }
+
+    pub fn select_next(&mut self) {
+        self.select_offset(1)
+    }
+
+    pub fn select_previous(&mut self) {
+        self.select_offset(-1)
+    }
+
+    pub fn select_first(&mut self) {
+        self.state.select(Some(0));
+        self.clamp();
+    }
+
+    pub fn select_last(&mut self) {
+        self.state.select(Some(self.len.saturating_sub(1)));
+        self.clamp();
+    }
+
+    fn select_offset(&mut self, offset: i64) {
+        let selected = self.state.selected().unwrap_or(0) as i64;
+        self.state
+            .select(Some((selected + offset).max(0).try_into().unwrap()));
+        self.clamp();
+    }
+
+    fn clamp(&mut self) {
+        if let Some(selected) = self.state.selected() {
+            self.state
+                .select(Some(selected.min(self.len.saturating_sub(1))));
+        }
+    }
+
+    /// Handle the selection keys, returns false if the key was not handled
+    pub(crate) fn handle(&mut self, key: &KeyName) -> bool {
+        match key {
+            KeyName::SelectNext => self.select_next(),
+            KeyName::SelectPrevious => self.select_previous(),
+            KeyName::SelectPageDown => self.select_offset(self.page_size as i64),
+            KeyName::SelectPageUp => self.select_offset(-(self.page_size as i64)),
+            KeyName::SelectFirst => self.select_first(),
+            KeyName::SelectLast => self.select_last(),
+            _ => return false,
+        };
+        true
+    }
+
+    pub fn header_style(&self) -> Style {
+        match self.focused {
+            true => Style::default().fg(Color::Green),
+            false => Style::default().fg(Color::DarkGray),
+        }
+    }
+
+    pub fn highlight_style(&self) -> Style {
+        match self.focused {
+            true => Style::default().add_modifier(Modifier::REVERSED),
+            false => Style::default(),
+        }
+    }
+
+    /// Area for the table, leaving the right most column for the indicator
+    pub fn table_area(&self, area: Rect) -> Rect {
+        Rect {
+            width: area.width.saturating_sub(1),
+            ..area
+        }
+    }
+
+    /// Draw a scroll bar in the right most column if the rows overflow
+    pub fn draw_indicator<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let height = area.height.saturating_sub(HEADER_HEIGHT) as usize;
+        if self.len <= height || height == 0 || area.width == 0 {
+            return;
+        }
+        let thumb = self.selected().unwrap_or(0) * (height - 1) / (self.len - 1).max(1);
+        let lines: Vec<Spans> = (0..height)
+            .map(|row| match row == thumb {
+                true => Spans::from(Span::styled("█", Style::default().fg(Color::Gray))),
+                false => Spans::from(Span::styled("│", Style::default().fg(Color::DarkGray))),
+            })
+            .collect();
+
+        f.render_widget(
+            Paragraph::new(lines),
+            Rect {
+                x: area.x + area.width - 1,
+                y: area.y + HEADER_HEIGHT,
+                width: 1,
+                height: height as u16,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn clamps_selection_to_rows() {
+        let mut scroll = TableScroll::new();
+        scroll.update(3, Rect::new(0, 0, 10, 10));
+        assert_eq!(Some(0), scroll.selected());
+
+        scroll.handle(&KeyName::SelectLast);
+        assert_eq!(Some(2), scroll.selected());
+        scroll.handle(&KeyName::SelectNext);
+        assert_eq!(Some(2), scroll.selected());
+
+        scroll.update(2, Rect::new(0, 0, 10, 10));
+        assert_eq!(Some(1), scroll.selected());
+
+        scroll.update(0, Rect::new(0, 0, 10, 10));
+        assert_eq!(None, scroll.selected());
+    }
+
+    #[test]
+    fn pages_by_visible_rows() {
+        let mut scroll = TableScroll::new();
+        scroll.update(100, Rect::new(0, 0, 10, 12));
+        scroll.handle(&KeyName::SelectPageDown);
+        assert_eq!(Some(10), scroll.selected());
+        scroll.handle(&KeyName::SelectPageDown);
+        assert_eq!(Some(20), scroll.selected());
+        scroll.handle(&KeyName::SelectPageUp);
+        assert_eq!(Some(10), scroll.selected());
+        scroll.handle(&KeyName::SelectFirst);
+        assert_eq!(Some(0), scroll.selected());
+        scroll.handle(&KeyName::SelectPrevious);
+        assert_eq!(Some(0), scroll.selected());
+    }
+
+    #[test]
+    fn does_not_handle_other_keys() {
+        let mut scroll = TableScroll::new();
+        assert!(!scroll.handle(&KeyName::Quit));
+    }
+}
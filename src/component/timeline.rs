@@ -0,0 +1,369 @@
+use anyhow::Ok;
+use chrono::Timelike;
+use tui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::config::{Config, KeyName},
+    model::model::{LogDay, LogEntry},
+    parser::token::TokenKind,
+};
+
+const PALETTE: &[Color] = &[
+    Color::Green,
+    Color::Cyan,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Blue,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightCyan,
+    Color::LightYellow,
+    Color::LightMagenta,
+    Color::LightBlue,
+];
+
+// e.g. "Mon 05 "
+const LABEL_WIDTH: u16 = 7;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColourBy {
+    Tag,
+    Project,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Mark {
+    Entry(usize),
+    Ongoing(usize),
+    Overlap,
+    Gap,
+}
+
+/// Each day's entries drawn as bars over the working hours, one row per day
+pub struct Timeline<'a> {
+    config: &'a Config,
+    colour_by: ColourBy,
+    offset: usize,
+}
+
+impl Timeline<'_> {
+    pub fn new(config: &Config) -> Timeline<'_> {
+        Timeline {
+            config,
+            colour_by: ColourBy::Tag,
+            offset: 0,
+        }
+    }
+
+    /// Rows needed to draw the given number of days, including the axis and
+    /// legend
+    pub fn height(days: usize) -> u16 {
+        days as u16 + 2
+    }
+
+    pub fn draw<B: tui::backend::Backend>(
+        &mut self,
+        f: &mut tui::Frame<B>,
+        area: Rect,
+        days: &[&LogDay],
+    ) -> anyhow::Result<()> {
+        let width = area.width.saturating_sub(LABEL_WIDTH) as usize;
+        if width == 0 || area.height < 3 {
+            return Ok(());
+        }
+        let visible = (area.height - 2) as usize;
+        self.offset = self.offset.min(days.len().saturating_sub(visible));
+
+        let mut labels: Vec<String> = vec![];
+        let mut lines = vec![self.axis(width)];
+        for day in days.iter().skip(self.offset).take(visible) {
+            let marks = self.marks(day, width, &mut labels);
+            let mut spans = vec![Span::styled(
+                format!(
+                    "{:<width$}",
+                    day.date().to_weekday_string(),
+                    width = LABEL_WIDTH as usize
+                ),
+                Style::default().fg(Color::DarkGray),
+            )];
+            spans.extend(marks.iter().map(|mark| match mark {
+                None => Span::raw(" "),
+                Some(Mark::Gap) => Span::styled("·", Style::default().fg(Color::DarkGray)),
+                Some(Mark::Overlap) => Span::styled("╳", Style::default().fg(Color::Red)),
+                Some(Mark::Entry(label)) => Span::styled("█", Style::default().fg(colour(*label))),
+                Some(Mark::Ongoing(label)) => {
+                    Span::styled("▒", Style::default().fg(colour(*label)))
+                }
+            }));
+            lines.push(Spans::from(spans));
+        }
+        lines.push(self.legend(&labels));
+
+        f.render_widget(Paragraph::new(lines), area);
+        Ok(())
+    }
+
+    pub(crate) fn handle(&mut self, key: &KeyName) -> bool {
+        match key {
+            KeyName::CycleColour => {
+                self.colour_by = match self.colour_by {
+                    ColourBy::Tag => ColourBy::Project,
+                    ColourBy::Project => ColourBy::Tag,
+                }
+            }
+            KeyName::SelectNext => self.offset = self.offset.saturating_add(1),
+            KeyName::SelectPrevious => self.offset = self.offset.saturating_sub(1),
+            KeyName::SelectFirst => self.offset = 0,
+            // clamped on draw
+            KeyName::SelectLast => self.offset = usize::MAX,
+            _ => return false,
+        };
+        true
+    }
+
+    fn axis_minutes(&self) -> (i64, i64) {
+        let start = self.config.working_hours.start.min(23) as i64 * 60;
+        let end = (self.config.working_hours.end.clamp(1, 24) as i64 * 60).max(start + 60);
+        (start, end)
+    }
+
+    fn column(&self, minutes: i64, width: usize) -> usize {
+        let (start, end) = self.axis_minutes();
+        let minutes = minutes.clamp(start, end);
+        (((minutes - start) * width as i64) as f64 / (end - start) as f64).round() as usize
+    }
+
+    fn axis<'a>(&self, width: usize) -> Spans<'a> {
+        let (start, end) = self.axis_minutes();
+        let hours = ((end - start) / 60) as usize;
+        // label every n hours so that the labels do not touch
+        let step = [1, 2, 3, 4, 6, 12, 24]
+            .into_iter()
+            .find(|step| step * width / hours.max(1) >= 3)
+            .unwrap_or(24);
+
+        let mut axis = vec![' '; width];
+        for hour in (0..hours).step_by(step) {
+            let column = self.column(start + hour as i64 * 60, width);
+            let label = format!("{:02}", (start / 60) as usize + hour);
+            for (offset, char) in label.chars().enumerate() {
+                if let Some(cell) = axis.get_mut(column + offset) {
+                    *cell = char;
+                }
+            }
+        }
+        Spans::from(vec![
+            Span::raw(" ".repeat(LABEL_WIDTH as usize)),
+            Span::styled(
+                axis.into_iter().collect::<String>(),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])
+    }
+
+    fn marks(&self, day: &LogDay, width: usize, labels: &mut Vec<String>) -> Vec<Option<Mark>> {
+        let mut marks = vec![None; width];
+        let ranges: Vec<(i64, i64, &LogEntry)> = day
+            .iter()
+            .map(|log| {
+                let start = minutes(log.time_range().start);
+                (
+                    start,
+                    start + log.time_range().duration().num_minutes(),
+                    log,
+                )
+            })
+            .filter(|(start, end, _)| end > start)
+            .collect();
+
+        for (start, end, log) in ranges.iter() {
+            let label = self.label(log);
+            let index = match labels.iter().position(|existing| *existing == label) {
+                Some(index) => index,
+                None => {
+                    labels.push(label);
+                    labels.len() - 1
+                }
+            };
+            let (from, to) = self.columns(*start, *end, width);
+            for mark in marks[from..to].iter_mut() {
+                *mark = Some(match log.time_range().ongoing {
+                    true => Mark::Ongoing(index),
+                    false => Mark::Entry(index),
+                });
+            }
+        }
+
+        for (index, (start, end, _)) in ranges.iter().enumerate() {
+            for (other_start, other_end, _) in ranges.iter().skip(index + 1) {
+                let (overlap_start, overlap_end) = (*start.max(other_start), *end.min(other_end));
+                if overlap_start < overlap_end {
+                    let (from, to) = self.columns(overlap_start, overlap_end, width);
+                    marks[from..to].fill(Some(Mark::Overlap));
+                }
+            }
+        }
+
+        let first = marks.iter().position(Option::is_some);
+        let last = marks.iter().rposition(Option::is_some);
+        if let (Some(first), Some(last)) = (first, last) {
+            for mark in marks[first..last].iter_mut().filter(|mark| mark.is_none()) {
+                *mark = Some(Mark::Gap);
+            }
+        }
+        marks
+    }
+
+    /// Columns covered by a range of minutes, at least one column if the
+    /// range is on the axis
+    fn columns(&self, start: i64, end: i64, width: usize) -> (usize, usize) {
+        let (axis_start, axis_end) = self.axis_minutes();
+        let from = self.column(start, width).min(width.saturating_sub(1));
+        let to = self.column(end, width);
+        match end > axis_start && start < axis_end {
+            true => (from, to.max(from + 1)),
+            false => (from, from),
+        }
+    }
+
+    fn label(&self, log: &LogEntry) -> String {
+        match self.colour_by {
+            ColourBy::Tag => log
+                .description()
+                .by_kind_refs(TokenKind::Tag)
+                .first()
+                .map(|tag| format!("@{}", tag.text()))
+                .unwrap_or_else(|| "untagged".to_string()),
+            ColourBy::Project => self
+                .config
+                .project_for(log.description())
+                .map(|project| project.name.to_string())
+                .unwrap_or_else(|| "no project".to_string()),
+        }
+    }
+
+    fn legend<'a>(&self, labels: &[String]) -> Spans<'a> {
+        let mut spans = vec![Span::styled(
+            match self.colour_by {
+                ColourBy::Tag => "by tag ",
+                ColourBy::Project => "by project ",
+            },
+            Style::default().fg(Color::DarkGray),
+        )];
+        for (index, label) in labels.iter().enumerate() {
+            spans.push(Span::styled("■ ", Style::default().fg(colour(index))));
+            spans.push(Span::raw(format!("{} ", label)));
+        }
+        spans.push(Span::styled(
+            " · gap ▒ ongoing ╳ overlap",
+            Style::default().fg(Color::DarkGray),
+        ));
+        Spans::from(spans)
+    }
+}
+
+fn minutes(time: chrono::NaiveTime) -> i64 {
+    (time.num_seconds_from_midnight() / 60) as i64
+}
+
+fn colour(index: usize) -> Color {
+    PALETTE[index % PALETTE.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use crate::parser::{
+        timesheet::{Date, Entry, Log, Time, TimeRange, Tokens},
+        token::Token,
+    };
+
+    use super::*;
+
+    fn log(start: (u32, u32), end: Option<(u32, u32)>, tag: &str) -> Log {
+        Log {
+            time: TimeRange {
+                start: Time::from_hm(start.0, start.1),
+                end: end.map(|(h, m)| Time::from_hm(h, m)),
+            },
+            description: Tokens::new(vec![Token::tag(tag.to_string())]).into(),
+            source: None,
+        }
+    }
+
+    fn day(logs: Vec<Log>) -> LogDay {
+        LogDay::new(
+            NaiveDate::from_ymd(2022, 1, 1).and_hms(12, 0, 0),
+            Entry {
+                date: Date::from_ymd(2022, 1, 1),
+                off: None,
+                logs,
+            },
+        )
+    }
+
+    fn render(marks: &[Option<Mark>]) -> String {
+        marks
+            .iter()
+            .map(|mark| match mark {
+                None => ' ',
+                Some(Mark::Gap) => '.',
+                Some(Mark::Overlap) => 'x',
+                Some(Mark::Entry(index)) => char::from_digit(*index as u32, 10).unwrap(),
+                Some(Mark::Ongoing(_)) => '>',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn marks_entries_gaps_and_ongoing() {
+        let config = Config::empty();
+        let timeline = Timeline::new(&config);
+        let day = day(vec![
+            log((2, 0), Some((4, 0)), "foo"),
+            log((6, 0), Some((8, 0)), "bar"),
+            log((8, 0), Some((9, 0)), "foo"),
+            log((10, 0), None, "baz"),
+        ]);
+        let mut labels = vec![];
+        assert_eq!(
+            "  00..110.>>            ",
+            render(&timeline.marks(&day, 24, &mut labels))
+        );
+        assert_eq!(vec!["@foo", "@bar", "@baz"], labels);
+    }
+
+    #[test]
+    fn marks_overlaps() {
+        let config = Config::empty();
+        let timeline = Timeline::new(&config);
+        let day = day(vec![
+            log((1, 0), Some((4, 0)), "foo"),
+            log((3, 0), Some((5, 0)), "bar"),
+        ]);
+        assert_eq!(
+            " 00x1                   ",
+            render(&timeline.marks(&day, 24, &mut vec![]))
+        );
+    }
+
+    #[test]
+    fn clips_to_working_hours() {
+        let config = Config {
+            working_hours: crate::app::config::WorkingHours { start: 8, end: 12 },
+            ..Config::empty()
+        };
+        let timeline = Timeline::new(&config);
+        let day = day(vec![
+            log((6, 0), Some((9, 0)), "foo"),
+            log((11, 0), Some((14, 0)), "bar"),
+        ]);
+        assert_eq!("0..1", render(&timeline.marks(&day, 4, &mut vec![])));
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::Ok;
+use tui::{
+    layout::{Constraint, Margin},
+    style::{Color, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, Clear, Row, Table},
+};
+
+use crate::{
+    app::{config::KeyName, ViewCommand},
+    model::model::{LogDate, LogDuration, LogEntry},
+    parser::token::{Token, TokenKind},
+    ui::centered_rect_absolute,
+};
+
+use super::{log_table::description, table_scroll::TableScroll};
+
+/// Popup listing every log entry which contains a given tag or ticket
+pub struct TokenEntries {
+    token: Option<Token>,
+    scroll: TableScroll,
+}
+
+impl TokenEntries {
+    pub(crate) fn new() -> TokenEntries {
+        TokenEntries {
+            token: None,
+            scroll: TableScroll::new(),
+        }
+    }
+
+    pub fn token(&self) -> Option<&Token> {
+        self.token.as_ref()
+    }
+
+    pub fn visible(&self) -> bool {
+        self.token.is_some()
+    }
+
+    pub fn show(&mut self, token: Token) {
+        self.token = Some(token);
+        self.scroll.focused = true;
+        self.scroll.select_first();
+    }
+
+    pub fn draw<B: tui::backend::Backend>(
+        &mut self,
+        f: &mut tui::Frame<B>,
+        logs: &[(&LogDate, &LogEntry)],
+    ) -> anyhow::Result<()> {
+        let token = match &self.token {
+            Some(token) => token,
+            None => return Ok(()),
+        };
+        let area = centered_rect_absolute(
+            100.min(f.size().width.saturating_sub(4)),
+            f.size().height.saturating_sub(4),
+            f.size(),
+        );
+
+        let headers = ["Date", "Time", "Duration", "Description"]
+            .iter()
+            .map(|header| Cell::from(Span::styled(*header, Style::default().fg(Color::DarkGray))));
+
+        let mut rows = vec![];
+        for (date, log) in logs.iter() {
+            rows.push(Row::new([
+                Cell::from(date.to_compact_string()),
+                Cell::from(Spans::from(vec![
+                    Span::raw(log.time_range().start.format("%H:%M").to_string()),
+                    Span::styled("-", Style::default().fg(Color::DarkGray)),
+                    Span::raw(log.time_range().end.format("%H:%M").to_string()),
+                ])),
+                Cell::from(log.time_range().duration().to_string()),
+                Cell::from(description(log.description())),
+            ]));
+        }
+
+        let total = LogDuration::from_minutes(
+            logs.iter()
+                .map(|(_, log)| log.time_range().duration().num_minutes())
+                .sum(),
+        );
+
+        let table = Table::new(rows)
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "{}: {} in {} entries [enter] apply as filter [esc] close",
+                token_label(token),
+                total,
+                logs.len()
+            )))
+            .header(Row::new(headers).height(1).bottom_margin(1))
+            .highlight_style(self.scroll.highlight_style())
+            .widths(&[
+                Constraint::Length(11),
+                Constraint::Length(12),
+                Constraint::Length(9),
+                Constraint::Percentage(100),
+            ]);
+
+        let inner = area.inner(&Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        self.scroll.update(logs.len(), inner);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(table, area, self.scroll.state());
+        self.scroll.draw_indicator(f, inner);
+        Ok(())
+    }
+
+    pub(crate) fn handle(&mut self, key: &KeyName) -> Option<ViewCommand> {
+        match key {
+            KeyName::Close => self.token = None,
+            KeyName::Select => {
+                return self
+                    .token
+                    .take()
+                    .map(|token| ViewCommand::ApplyFilter(token_label(&token)))
+            }
+            _ => {
+                self.scroll.handle(key);
+            }
+        };
+        None
+    }
+}
+
+fn token_label(token: &Token) -> String {
+    match token.kind {
+        TokenKind::Tag => format!("@{}", token.text()),
+        _ => token.text().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn applies_token_as_filter_on_select() {
+        let mut entries = TokenEntries::new();
+        entries.show(Token::tag("foobar".to_string()));
+        assert!(entries.visible());
+
+        match entries.handle(&KeyName::Select) {
+            Some(ViewCommand::ApplyFilter(filter)) => assert_eq!("@foobar", filter),
+            _ => panic!("Expected filter command"),
+        }
+        assert!(!entries.visible());
+    }
+
+    #[test]
+    fn closes_on_close() {
+        let mut entries = TokenEntries::new();
+        entries.show(Token::ticket("FOO-1".to_string()));
+        assert!(entries.handle(&KeyName::Close).is_none());
+        assert!(!entries.visible());
+    }
+}
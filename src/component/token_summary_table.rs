@@ -7,58 +7,166 @@ use tui::{
 };
 
 use crate::{
-    model::model::{TagMeta, TagMetas},
-    parser::token::TokenKind,
+    model::{
+        model::{SortKey, SortOrder, TagMeta, TagMetas, TokenSeries},
+        rounding::{self, RoundedTokens},
+    },
+    parser::token::{Token, TokenKind},
 };
 
+use super::{progress::delta, table_scroll::TableScroll};
+
+/// Number of periods shown in the trend column
+pub const TREND_PERIODS: usize = 8;
+
 pub struct TokenSummaryTable<'a> {
     title: &'a str,
+    pub scroll: TableScroll,
+    selected: Option<Token>,
+    sort_key: SortKey,
+    sort_order: SortOrder,
 }
 
 impl TokenSummaryTable<'_> {
     pub fn new(title: &str) -> TokenSummaryTable<'_> {
-        TokenSummaryTable { title }
+        TokenSummaryTable {
+            title,
+            scroll: TableScroll::new(),
+            selected: None,
+            sort_key: SortKey::Duration,
+            sort_order: SortOrder::Descending,
+        }
     }
 
     pub fn draw<B: tui::backend::Backend>(
-        &self,
+        &mut self,
         f: &mut tui::Frame<B>,
         area: tui::layout::Rect,
-        tag_metas: &TagMetas,
+        mut tag_metas: TagMetas,
+        trends: Option<&TokenSeries>,
+        rounded: Option<&RoundedTokens>,
     ) -> anyhow::Result<()> {
+        let side_by_side = rounded.is_some_and(|rounded| rounded.side_by_side);
+        let rounded_minutes =
+            |tag_meta: &TagMeta| rounded.and_then(|rounded| rounded.get(&tag_meta.tag));
+        tag_metas.sort(self.sort_key, self.sort_order);
+
         let mut rows = vec![];
-        let binding = [self.title, "Duration", "Count"];
-        let headers = binding
-            .iter()
-            .map(|header| Cell::from(Span::styled(*header, Style::default().fg(Color::DarkGray))));
+        let header_style = self.scroll.header_style();
+        let mut headers: Vec<Cell> = [
+            (self.title, SortKey::Name),
+            ("Duration", SortKey::Duration),
+            ("Share", SortKey::Share),
+            ("Count", SortKey::Count),
+        ]
+        .map(|(header, key)| {
+            Cell::from(Span::styled(
+                match key == self.sort_key {
+                    true => format!("{} {}", header, sort_indicator(self.sort_order)),
+                    false => header.to_string(),
+                },
+                header_style,
+            ))
+        })
+        .to_vec();
+        if trends.is_some() {
+            headers.push(Cell::from(Span::styled("Trend", header_style)));
+            headers.push(Cell::from(Span::styled("Change", header_style)));
+        }
 
         for tag_meta in tag_metas.tag_metas.iter() {
-            rows.push(Row::new([
-                Cell::from((|t: &TagMeta| match tag_meta.kind {
-                    TokenKind::Tag => {
-                        Span::styled(format!("@{}", t.tag), Style::default().fg(Color::Green))
-                    }
-                    TokenKind::Prose => Span::raw(t.tag.to_owned()),
-                    TokenKind::Ticket => {
-                        Span::styled(t.tag.to_string(), Style::default().fg(Color::Cyan))
-                    }
-                })(tag_meta)),
-                Cell::from(tag_meta.duration.to_string()),
+            let mut cells = vec![
+                Cell::from(token_span(tag_meta)),
+                Cell::from(rounding::format(
+                    tag_meta.duration.num_minutes(),
+                    rounded_minutes(tag_meta),
+                    side_by_side,
+                )),
+                Cell::from(format!("{:.1}%", tag_metas.share(tag_meta))),
                 Cell::from(tag_meta.count.to_string()),
-            ]));
+            ];
+            if let Some(trends) = trends {
+                cells.push(Cell::from(Span::styled(
+                    sparkline(trends.get(&tag_meta.tag)),
+                    Style::default().fg(Color::Yellow),
+                )));
+                cells.push(Cell::from(delta(trends.delta(&tag_meta.tag))));
+            }
+            rows.push(Row::new(cells));
         }
 
-        rows.push(Row::new([
-            Cell::default(),
-            Cell::default(),
-            Cell::default(),
-        ]));
+        // only if the rounding applies to the tokens of the table
+        let rounded_total = tag_metas
+            .iter()
+            .any(|tag_meta| rounded_minutes(tag_meta).is_some())
+            .then(|| {
+                tag_metas
+                    .iter()
+                    .map(|tag_meta| {
+                        rounded_minutes(tag_meta).unwrap_or(tag_meta.duration.num_minutes())
+                    })
+                    .sum()
+            });
+        rows.push(Row::new([Cell::default()]));
         rows.push(Row::new([
             Cell::from(Span::styled("Total:", Style::default().fg(Color::DarkGray))),
-            Cell::from(Span::raw(tag_metas.duration().to_string())),
+            Cell::from(Span::raw(rounding::format(
+                tag_metas.duration().num_minutes(),
+                rounded_total,
+                side_by_side,
+            ))),
+            Cell::default(),
             Cell::default(),
         ]));
+        // entries with several tokens count towards each of them in full
+        let overlap = tag_metas.overlap();
+        if overlap.num_minutes() > 0 {
+            let label = Style::default().fg(Color::DarkGray);
+            rows.push(Row::new([
+                Cell::from(Span::styled("Logged:", label)),
+                Cell::from(tag_metas.logged.to_string()),
+            ]));
+            rows.push(Row::new([
+                Cell::from(Span::styled("Overlap:", label)),
+                Cell::from(Span::styled(
+                    overlap.to_string(),
+                    Style::default().fg(Color::Yellow),
+                )),
+            ]));
+        }
 
+        self.scroll.update(tag_metas.len(), area);
+        self.selected = self
+            .scroll
+            .selected()
+            .map(|index| token(&tag_metas.tag_metas[index]));
+
+        // the raw and rounded duration need more space
+        let duration_width = match side_by_side {
+            true => 17,
+            false => 10,
+        };
+        let widths = match trends {
+            Some(_) => vec![
+                // the remaining width after the other columns and their spacing
+                Constraint::Length(
+                    area.width
+                        .saturating_sub(35 + duration_width + TREND_PERIODS as u16)
+                        .max(8),
+                ),
+                Constraint::Length(duration_width),
+                Constraint::Length(7),
+                Constraint::Length(6),
+                Constraint::Length(TREND_PERIODS as u16),
+                Constraint::Length(8),
+            ],
+            None => vec![
+                Constraint::Percentage(35),
+                Constraint::Length(duration_width),
+                Constraint::Length(7),
+                Constraint::Length(7),
+            ],
+        };
         let table = Table::new(rows)
             .header(
                 Row::new(headers)
@@ -66,12 +174,73 @@ impl TokenSummaryTable<'_> {
                     .bottom_margin(1)
                     .style(Style::default()),
             )
-            .widths(&[
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-            ]);
-        f.render_widget(table, area);
+            .highlight_style(self.scroll.highlight_style())
+            .widths(&widths);
+        f.render_stateful_widget(table, self.scroll.table_area(area), self.scroll.state());
+        self.scroll.draw_indicator(f, area);
         Ok(())
     }
+
+    pub fn cycle_sort(&mut self) {
+        self.sort_key = self.sort_key.next();
+    }
+
+    pub fn reverse_sort(&mut self) {
+        self.sort_order = self.sort_order.reverse();
+    }
+
+    /// The token of the selected row as of the last draw
+    pub fn selected(&self) -> Option<&Token> {
+        self.selected.as_ref()
+    }
+}
+
+/// Minutes per period as a bar per period, scaled to the busiest period
+pub(crate) fn sparkline(values: &[i64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    values
+        .iter()
+        .map(|value| BARS[((*value).max(0) * (BARS.len() as i64 - 1) / max) as usize])
+        .collect()
+}
+
+fn sort_indicator(order: SortOrder) -> &'static str {
+    match order {
+        SortOrder::Ascending => "▲",
+        SortOrder::Descending => "▼",
+    }
+}
+
+fn token(tag_meta: &TagMeta) -> Token {
+    match tag_meta.kind {
+        TokenKind::Tag => Token::tag(tag_meta.tag.to_string()),
+        TokenKind::Ticket => Token::ticket(tag_meta.tag.to_string()),
+        TokenKind::Prose => Token::prose(tag_meta.tag.to_string()),
+    }
+}
+
+fn token_span<'a>(tag_meta: &TagMeta) -> Span<'a> {
+    match tag_meta.kind {
+        TokenKind::Tag => Span::styled(
+            format!("@{}", tag_meta.tag),
+            Style::default().fg(Color::Green),
+        ),
+        TokenKind::Prose => Span::raw(tag_meta.tag.to_owned()),
+        TokenKind::Ticket => {
+            Span::styled(tag_meta.tag.to_string(), Style::default().fg(Color::Cyan))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sparkline_scales_to_max() {
+        assert_eq!("▁▄█▁", sparkline(&[0, 60, 120, 1]));
+        assert_eq!("▁▁", sparkline(&[0, 0]));
+        assert_eq!("", sparkline(&[]));
+    }
 }
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use itertools::Itertools;
+
+use crate::{model::model::LogDays, parser::token::TokenKind};
+
+const FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// Entries as an iCalendar with an event for each entry with a duration.
+/// The UID of an event is made of its date and start, so that importing
+/// again updates the events.
+pub fn ics(log_days: &LogDays, stamp: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//pttlog//pttlogger//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for day in log_days.iter() {
+        let date = day.date().naive_date();
+        let mut starts: HashMap<NaiveDateTime, usize> = HashMap::new();
+        for entry in day.iter() {
+            let (start, end) = entry.time_range().date_times(date);
+            // entries starting at the same time are told apart by their order
+            let occurrence = starts.entry(start).or_insert(0);
+            *occurrence += 1;
+            if start == end {
+                continue;
+            }
+            let tokens = |kind| -> Vec<&str> {
+                entry
+                    .description()
+                    .by_kind_refs(kind)
+                    .iter()
+                    .map(|token| token.text())
+                    .unique()
+                    .collect()
+            };
+            let (tags, tickets) = (tokens(TokenKind::Tag), tokens(TokenKind::Ticket));
+            let prose = entry.description().by_kind(TokenKind::Prose).to_string();
+            let summary = match prose.trim() {
+                "" => tickets.iter().chain(tags.iter()).join(" "),
+                prose => prose.to_string(),
+            };
+
+            lines.push("BEGIN:VEVENT".to_string());
+            let uid = match occurrence {
+                1 => start.format(FORMAT).to_string(),
+                _ => format!("{}-{}", start.format(FORMAT), occurrence),
+            };
+            lines.push(format!("UID:{}@pttlog", uid));
+            lines.push(format!("DTSTAMP:{}Z", stamp.format(FORMAT)));
+            lines.push(format!("DTSTART:{}", start.format(FORMAT)));
+            lines.push(format!("DTEND:{}", end.format(FORMAT)));
+            lines.push(format!("SUMMARY:{}", escape(&summary)));
+            if !tags.is_empty() {
+                lines.push(format!(
+                    "CATEGORIES:{}",
+                    tags.iter().map(|tag| escape(tag)).join(",")
+                ));
+            }
+            if !tickets.is_empty() {
+                lines.push(format!(
+                    "X-PTTLOG-TICKET:{}",
+                    tickets.iter().map(|ticket| escape(ticket)).join(",")
+                ));
+            }
+            lines.push("END:VEVENT".to_string());
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold(line) + "\r\n").collect()
+}
+
+/// Escape a text value
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line into lines of at most 75 octets, continued with a
+/// space
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{NaiveDate, TimeZone};
+
+    use crate::{
+        app::config::{Config, Project},
+        parser::timesheet::parse_entry,
+    };
+
+    use super::*;
+
+    #[test]
+    fn writes_an_event_per_entry() {
+        let timesheet = "2026-01-05\n\
+            09:00 @acme @review EXAMPLE-1 pairing; on it, mostly\n\
+            10:00-10:00 EXAMPLE-2 nothing\n\
+            10:00-11:00 EXAMPLE-2 @acme\n";
+        let config = Config {
+            projects: vec![Project {
+                name: "example".to_string(),
+                ticket_prefix: "EXAMPLE-".to_string(),
+                tags: vec![],
+                rate: None,
+                recipient: None,
+            }],
+            ..Config::empty()
+        };
+        let entries = parse_entry(timesheet, &config).unwrap().1.entries;
+        let log_days =
+            LogDays::from_entries(NaiveDate::from_ymd(2026, 1, 6).and_hms(0, 0, 0), entries);
+        let ics = ics(&log_days, Utc.ymd(2026, 1, 6).and_hms(12, 0, 0));
+        assert_eq!(
+            "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            PRODID:-//pttlog//pttlogger//EN\r\n\
+            CALSCALE:GREGORIAN\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:20260105T090000@pttlog\r\n\
+            DTSTAMP:20260106T120000Z\r\n\
+            DTSTART:20260105T090000\r\n\
+            DTEND:20260105T100000\r\n\
+            SUMMARY:pairing\\; on it\\, mostly\r\n\
+            CATEGORIES:acme,review\r\n\
+            X-PTTLOG-TICKET:EXAMPLE-1\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:20260105T100000-2@pttlog\r\n\
+            DTSTAMP:20260106T120000Z\r\n\
+            DTSTART:20260105T100000\r\n\
+            DTEND:20260105T110000\r\n\
+            SUMMARY:EXAMPLE-2 acme\r\n\
+            CATEGORIES:acme\r\n\
+            X-PTTLOG-TICKET:EXAMPLE-2\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n",
+            ics
+        );
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+        let folded = fold(&line);
+        assert!(folded.split("\r\n").all(|line| line.len() <= 75));
+        assert_eq!(line, folded.replace("\r\n ", ""));
+    }
+}
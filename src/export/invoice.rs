@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use chrono::{Duration, NaiveDate};
+use serde_derive::Serialize;
+
+use crate::{
+    app::config::{Config, InvoiceItems, Project},
+    model::{model::LogDays, rounding},
+    parser::token::TokenKind,
+};
+
+/// Invoice metadata as expected by `invoice-from-metadata` of the typst
+/// invoice template
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Invoice {
+    pub language: String,
+    pub currency: String,
+    pub invoice_id: String,
+    pub issuing_date: NaiveDate,
+    /// Last day of the period invoiced
+    pub delivery_date: NaiveDate,
+    pub due_date: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub biller: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<serde_json::Value>,
+    pub hourly_rate: f64,
+    pub items: Vec<Item>,
+    pub hours: f64,
+    pub sub_total: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax: Option<f64>,
+    pub total: f64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Item {
+    pub number: usize,
+    pub date: NaiveDate,
+    pub description: String,
+    pub dur_min: i64,
+    pub hours: f64,
+    pub quantity: u32,
+    pub price: f64,
+}
+
+/// Format the invoice is written in
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum Format {
+    Yaml,
+    Json,
+}
+
+impl Invoice {
+    /// Invoice of the time logged for the project from `start` until
+    /// (excluding) `end`, rounded as configured
+    pub fn new(
+        config: &Config,
+        log_days: &LogDays,
+        project: &Project,
+        id: String,
+        (start, end): (NaiveDate, NaiveDate),
+        items: InvoiceItems,
+        issuing_date: NaiveDate,
+    ) -> Result<Invoice> {
+        let rate = match project.rate {
+            Some(rate) => rate,
+            None => bail!("no rate configured for project \"{}\"", project.name),
+        };
+        let log_days = log_days.until(start, end).retain(|entry| {
+            config
+                .project_for(entry.description())
+                .is_some_and(|entry_project| entry_project.name == project.name)
+        });
+        let minutes = match items {
+            InvoiceItems::Ticket => by_ticket(config, &log_days),
+            InvoiceItems::Day => by_day(config, &log_days),
+        };
+
+        let items: Vec<Item> = minutes
+            .into_iter()
+            .enumerate()
+            .map(|(index, (date, description, minutes))| Item {
+                number: index + 1,
+                date,
+                description,
+                dur_min: minutes,
+                hours: hours(minutes),
+                quantity: 1,
+                price: cents(minutes as f64 / 60.0 * rate),
+            })
+            .collect();
+        let sub_total = cents(items.iter().map(|item| item.price).sum());
+        let tax = config.invoice.tax;
+        Ok(Invoice {
+            language: config.invoice.language.to_string(),
+            currency: config.invoice.currency.to_string(),
+            invoice_id: id,
+            issuing_date,
+            delivery_date: end - Duration::days(1),
+            due_date: issuing_date + Duration::days(config.invoice.due_days as i64),
+            biller: config.invoice.biller.clone(),
+            recipient: project.recipient.clone(),
+            hourly_rate: rate,
+            hours: hours(items.iter().map(|item| item.dur_min).sum()),
+            items,
+            sub_total,
+            tax,
+            total: cents(sub_total * (1.0 + tax.unwrap_or(0.0))),
+        })
+    }
+
+    pub fn to_string(&self, format: Format) -> Result<String> {
+        Ok(match format {
+            Format::Yaml => serde_yaml::to_string(self)?,
+            Format::Json => serde_json::to_string_pretty(self)? + "\n",
+        })
+    }
+}
+
+/// Date of the last entry, ticket and minutes of each ticket, entries
+/// without a ticket are billed together
+fn by_ticket(config: &Config, log_days: &LogDays) -> Vec<(NaiveDate, String, i64)> {
+    let mut dates: HashMap<Option<String>, NaiveDate> = HashMap::new();
+    for day in log_days.iter() {
+        for entry in day.iter() {
+            let tickets = entry.description().by_kind_refs(TokenKind::Ticket);
+            if tickets.is_empty() {
+                dates.insert(None, day.date().naive_date());
+            }
+            for ticket in tickets {
+                dates.insert(Some(ticket.text().to_string()), day.date().naive_date());
+            }
+        }
+    }
+    let minutes = rounding::by_item(config.rounding.as_ref(), log_days, |_, ticket| {
+        // tickets first, then the entries without one
+        (ticket.is_none(), ticket.map(str::to_string))
+    });
+    minutes
+        .into_iter()
+        .map(|((_, ticket), minutes)| {
+            let date = dates[&ticket];
+            (date, ticket.unwrap_or("Other".to_string()), minutes)
+        })
+        .collect()
+}
+
+/// Date, tickets and minutes of each day with time logged
+fn by_day(config: &Config, log_days: &LogDays) -> Vec<(NaiveDate, String, i64)> {
+    let minutes = rounding::by_item(config.rounding.as_ref(), log_days, |day, _| {
+        day.date().naive_date()
+    });
+    log_days
+        .iter()
+        .filter(|day| day.duration_total().num_minutes() > 0)
+        .map(|day| {
+            let date = day.date().naive_date();
+            let tickets = day
+                .tag_summary(TokenKind::Ticket, log_days.allocation())
+                .iter()
+                .map(|meta| meta.tag.to_string())
+                .collect::<Vec<String>>();
+            (date, tickets.join(", "), minutes[&date])
+        })
+        .collect()
+}
+
+fn hours(minutes: i64) -> f64 {
+    cents(minutes as f64 / 60.0)
+}
+
+fn cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        app::config::{Allocation, Rounding, RoundingMode, RoundingScope},
+        parser::timesheet::parse_entry,
+    };
+
+    use super::*;
+
+    fn rounding(scope: RoundingScope) -> Rounding {
+        Rounding {
+            mode: RoundingMode::Up,
+            minutes: 15,
+            scope,
+            side_by_side: false,
+        }
+    }
+
+    fn config(rounding: Option<Rounding>) -> Config {
+        Config {
+            rounding,
+            projects: vec![Project {
+                name: "acme".to_string(),
+                ticket_prefix: "ACME-".to_string(),
+                tags: vec!["acme".to_string()],
+                rate: Some(100.0),
+                recipient: None,
+            }],
+            ..Config::empty()
+        }
+    }
+
+    fn invoice(config: &Config, items: InvoiceItems) -> Invoice {
+        let timesheet = "2026-01-05\n\
+            09:00-09:50 ACME-1 design\n\
+            10:00-10:20 @acme call\n\
+            11:00-12:00 @other elsewhere\n\
+            \n\
+            2026-01-06\n\
+            09:00-10:00 ACME-1 build\n\
+            10:00-10:10 ACME-2 fix\n\
+            \n\
+            2026-02-02\n\
+            09:00-10:00 ACME-3 later\n";
+        let entries = parse_entry(timesheet, config).unwrap().1.entries;
+        let range = (
+            NaiveDate::from_ymd(2026, 1, 1),
+            NaiveDate::from_ymd(2026, 2, 1),
+        );
+        let project = &config.projects[0];
+        let today = NaiveDate::from_ymd(2026, 2, 1);
+        Invoice::new(
+            config,
+            &LogDays::new(entries),
+            project,
+            "DTL-1".to_string(),
+            range,
+            items,
+            today,
+        )
+        .unwrap()
+    }
+
+    fn items(invoice: &Invoice) -> Vec<(String, i64)> {
+        invoice
+            .items
+            .iter()
+            .map(|item| (item.description.to_string(), item.dur_min))
+            .collect()
+    }
+
+    #[test]
+    fn invoices_tickets_of_the_project() {
+        let invoice = invoice(&config(None), InvoiceItems::Ticket);
+        assert_eq!(
+            vec![
+                ("ACME-1".to_string(), 110),
+                ("ACME-2".to_string(), 10),
+                ("Other".to_string(), 20)
+            ],
+            items(&invoice)
+        );
+        assert_eq!(NaiveDate::from_ymd(2026, 1, 6), invoice.items[0].date);
+        assert_eq!(183.33, invoice.items[0].price);
+        assert_eq!(2.33, invoice.hours);
+        assert_eq!(233.33, invoice.sub_total);
+        assert_eq!(NaiveDate::from_ymd(2026, 2, 15), invoice.due_date);
+    }
+
+    #[test]
+    fn invoices_days_with_rounding() {
+        let by_day = invoice(
+            &config(Some(rounding(RoundingScope::Day))),
+            InvoiceItems::Day,
+        );
+        assert_eq!(
+            vec![
+                ("ACME-1".to_string(), 75),
+                ("ACME-1, ACME-2".to_string(), 75)
+            ],
+            items(&by_day)
+        );
+
+        // 140m rounded to 150m and split by the minutes of each ticket
+        let by_ticket = invoice(
+            &config(Some(rounding(RoundingScope::Interval))),
+            InvoiceItems::Ticket,
+        );
+        assert_eq!(
+            vec![
+                ("ACME-1".to_string(), 118),
+                ("ACME-2".to_string(), 11),
+                ("Other".to_string(), 21),
+            ],
+            items(&by_ticket)
+        );
+        assert_eq!(2.5, by_ticket.hours);
+    }
+
+    #[test]
+    fn items_of_entries_with_several_tickets_add_up_to_the_rounded_total() {
+        let timesheet = "2026-01-05\n\
+            09:00-09:20 ACME-1 ACME-2 pairing\n\
+            09:20-09:32 ACME-1\n\
+            \n\
+            2026-01-06\n\
+            09:00-09:40 ACME-1:75% ACME-2 review\n";
+        let range = (
+            NaiveDate::from_ymd(2026, 1, 1),
+            NaiveDate::from_ymd(2026, 2, 1),
+        );
+        let today = NaiveDate::from_ymd(2026, 2, 1);
+        let bill = |config: &Config, log_days: &LogDays, items| {
+            let project = &config.projects[0];
+            Invoice::new(
+                config,
+                log_days,
+                project,
+                "DTL-1".to_string(),
+                range,
+                items,
+                today,
+            )
+            .unwrap()
+        };
+        for allocation in [Allocation::Full, Allocation::Even, Allocation::Weighted] {
+            for scope in [
+                RoundingScope::Entry,
+                RoundingScope::Day,
+                RoundingScope::Ticket,
+                RoundingScope::Interval,
+            ] {
+                let config = config(Some(rounding(scope)));
+                let entries = parse_entry(timesheet, &config).unwrap().1.entries;
+                let log_days = LogDays::new(entries).with_allocation(allocation);
+                let total = rounding::total(&rounding(scope), &log_days);
+                for items in [InvoiceItems::Ticket, InvoiceItems::Day] {
+                    let invoice = bill(&config, &log_days, items);
+                    let billed: i64 = invoice.items.iter().map(|item| item.dur_min).sum();
+                    assert_eq!(total, billed, "{:?} {:?} {:?}", allocation, scope, items);
+                    assert!(invoice
+                        .items
+                        .iter()
+                        .all(|item| item.description != "Rounding"));
+                }
+            }
+        }
+
+        // the day's 32m rounded to 45m and split by the ticket's share of each entry
+        let config = config(Some(rounding(RoundingScope::Day)));
+        let first_day = &timesheet[..timesheet.find("\n\n").unwrap()];
+        let entries = parse_entry(first_day, &config).unwrap().1.entries;
+        let log_days = LogDays::new(entries).with_allocation(Allocation::Even);
+        let invoice = bill(&config, &log_days, InvoiceItems::Ticket);
+        assert_eq!(
+            vec![("ACME-1".to_string(), 31), ("ACME-2".to_string(), 14)],
+            items(&invoice)
+        );
+    }
+
+    #[test]
+    fn writes_kebab_case_keys() {
+        let mut config = config(None);
+        config.invoice.tax = Some(0.19);
+        let yaml = invoice(&config, InvoiceItems::Ticket)
+            .to_string(Format::Yaml)
+            .unwrap();
+        assert!(yaml.contains("invoice-id: DTL-1\n"));
+        assert!(yaml.contains("issuing-date: 2026-02-01\n"));
+        assert!(yaml.contains("dur-min: 110\n"));
+        assert!(yaml.contains("total: 277.66\n"));
+        assert!(!yaml.contains("biller"));
+    }
+}
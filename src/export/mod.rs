@@ -0,0 +1,4 @@
+pub mod ics;
+pub mod invoice;
+pub mod report;
+pub mod timeclock;
@@ -0,0 +1,584 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
+use itertools::Itertools;
+
+use crate::{
+    app::config::Config,
+    component::progress,
+    model::{
+        model::{LogDays, LogDuration, SortKey, SortOrder, TagMetas},
+        rounding,
+        target::Schedule,
+    },
+    parser::{
+        timesheet::Tokens,
+        token::{Token, TokenKind},
+    },
+};
+
+/// Table of a report
+struct Table {
+    title: &'static str,
+    headers: Vec<&'static str>,
+    /// Columns from this one on hold durations or numbers
+    numeric_from: usize,
+    rows: Vec<Vec<String>>,
+    /// Totals below the rows
+    footer: Vec<Vec<String>>,
+}
+
+/// The Summary and List of an interval: tag, ticket and project tables,
+/// the weekday breakdown and the duration of each day
+pub struct Report {
+    title: String,
+    progress: String,
+    tables: Vec<Table>,
+    weekdays: Vec<(String, u64)>,
+}
+
+impl Report {
+    /// Report of the days from `start` until (excluding) `end`
+    pub fn new(
+        config: &Config,
+        log_days: &LogDays,
+        (start, end): (NaiveDate, NaiveDate),
+        today: NaiveDate,
+    ) -> Report {
+        let log_days = log_days.until(start, end);
+        let schedule = Schedule::new(config, &log_days);
+        let progress = schedule.progress(&log_days.minutes_by_date(), start, end, today);
+        let mut summary = format!(
+            "{} / {} {}",
+            log_days.duration_total(),
+            LogDuration::from_minutes(progress.target),
+            signed(progress.over_under())
+        );
+        if let Some(days_off) = progress::days_off(&schedule.days_off(start, end)) {
+            summary.push_str(&format!(" ({})", days_off));
+        }
+
+        let mut tables = vec![
+            token_table("Tags", config, &log_days, TokenKind::Tag),
+            token_table("Tickets", config, &log_days, TokenKind::Ticket),
+            project_table(config, &log_days),
+            weekday_table(&log_days, &schedule),
+        ];
+        tables.extend(time_off_table(&schedule, (start, end)));
+        tables.push(list_table(config, &log_days, &schedule));
+
+        Report {
+            title: format!("{} to {}", start, end - Duration::days(1)),
+            progress: summary,
+            tables,
+            weekdays: log_days
+                .minutes_by_weekday()
+                .into_iter()
+                .map(|(day, minutes)| (day.to_string(), minutes))
+                .collect(),
+        }
+    }
+
+    /// GitHub flavoured markdown with a table for each part
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Report {}\n\n{}\n", self.title, self.progress);
+        for table in self.tables.iter() {
+            markdown.push_str(&format!("\n## {}\n\n", table.title));
+            let row = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+            markdown.push_str(&row(table
+                .headers
+                .iter()
+                .map(|header| header.to_string())
+                .collect()));
+            markdown.push_str(&row((0..table.headers.len())
+                .map(|column| match column >= table.numeric_from {
+                    true => "---:".to_string(),
+                    false => "---".to_string(),
+                })
+                .collect()));
+            for cells in table.rows.iter() {
+                markdown.push_str(&row(cells
+                    .iter()
+                    .map(|cell| escape_markdown(cell))
+                    .collect()));
+            }
+            for cells in table.footer.iter() {
+                markdown.push_str(&row(padded(cells, table.headers.len())
+                    .enumerate()
+                    .map(|(column, cell)| match (column, cell.is_empty()) {
+                        (0, false) => format!("**{}**", escape_markdown(cell)),
+                        _ => escape_markdown(cell),
+                    })
+                    .collect()));
+            }
+        }
+        markdown
+    }
+
+    /// A single HTML page with its styles inline and a bar chart of the
+    /// time logged by weekday
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            "<!DOCTYPE html>\n\
+            <html>\n\
+            <head>\n\
+            <meta charset=\"utf-8\">\n\
+            <title>Report {title}</title>\n\
+            <style>\n{style}</style>\n\
+            </head>\n\
+            <body>\n\
+            <h1>Report {title}</h1>\n\
+            <p class=\"progress\">{progress}</p>\n",
+            title = escape_html(&self.title),
+            style = STYLE,
+            progress = escape_html(&self.progress),
+        );
+        for table in self.tables.iter() {
+            html.push_str(&format!("<h2>{}</h2>\n", table.title));
+            if table.title == "Weekdays" {
+                html.push_str(&self.chart());
+            }
+            html.push_str("<table>\n<thead>\n");
+            let row = |cells: &mut dyn Iterator<Item = &String>, tag: &str| {
+                let cells: String = cells
+                    .enumerate()
+                    .map(|(column, cell)| match column >= table.numeric_from {
+                        true => format!("<{tag} class=\"numeric\">{}</{tag}>", escape_html(cell)),
+                        false => format!("<{tag}>{}</{tag}>", escape_html(cell)),
+                    })
+                    .collect();
+                format!("<tr>{}</tr>\n", cells)
+            };
+            let headers: Vec<String> = table
+                .headers
+                .iter()
+                .map(|header| header.to_string())
+                .collect();
+            html.push_str(&row(&mut headers.iter(), "th"));
+            html.push_str("</thead>\n<tbody>\n");
+            for cells in table.rows.iter() {
+                html.push_str(&row(&mut cells.iter(), "td"));
+            }
+            html.push_str("</tbody>\n");
+            if !table.footer.is_empty() {
+                html.push_str("<tfoot>\n");
+                for cells in table.footer.iter() {
+                    html.push_str(&row(&mut padded(cells, table.headers.len()), "td"));
+                }
+                html.push_str("</tfoot>\n");
+            }
+            html.push_str("</table>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// SVG bars of the minutes by weekday, scaled to the busiest day
+    fn chart(&self) -> String {
+        const BAR: u64 = 40;
+        const GAP: u64 = 16;
+        const HEIGHT: u64 = 120;
+        let max = self
+            .weekdays
+            .iter()
+            .map(|(_, minutes)| *minutes)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let width = self.weekdays.len() as u64 * (BAR + GAP) + GAP;
+        let mut svg = format!(
+            "<svg class=\"chart\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+            width,
+            HEIGHT + 40,
+            width,
+            HEIGHT + 40
+        );
+        for (index, (day, minutes)) in self.weekdays.iter().enumerate() {
+            let x = GAP + index as u64 * (BAR + GAP);
+            let height = minutes * HEIGHT / max;
+            let y = 20 + HEIGHT - height;
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"><title>{}</title></rect>\n",
+                x,
+                y,
+                BAR,
+                height,
+                LogDuration::from_minutes(*minutes as i64)
+            ));
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\">{}</text>\n",
+                x + BAR / 2,
+                y - 4,
+                LogDuration::from_minutes(*minutes as i64)
+            ));
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\">{}</text>\n",
+                x + BAR / 2,
+                HEIGHT + 36,
+                day
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }\n\
+    .progress { color: #555; }\n\
+    table { border-collapse: collapse; margin-bottom: 1em; }\n\
+    th, td { padding: 0.25em 0.75em; border-bottom: 1px solid #ddd; text-align: left; }\n\
+    .numeric { text-align: right; }\n\
+    tfoot td { font-weight: bold; border-bottom: none; }\n\
+    .chart rect { fill: #4a90d9; }\n\
+    .chart text { font-size: 11px; text-anchor: middle; fill: #555; }\n";
+
+/// Duration, share and count of each token of the kind, longest first
+fn token_table(title: &'static str, config: &Config, log_days: &LogDays, kind: TokenKind) -> Table {
+    let rounded = config
+        .rounding
+        .as_ref()
+        .map(|rounding| rounding::token_totals(rounding, log_days, kind));
+    let side_by_side = rounded.as_ref().is_some_and(|rounded| rounded.side_by_side);
+    let rounded_minutes = |tag: &str| rounded.as_ref().and_then(|rounded| rounded.get(tag));
+    let mut tag_metas: TagMetas = log_days.tag_summary(kind);
+    tag_metas.sort(SortKey::Duration, SortOrder::Descending);
+
+    let rows = tag_metas
+        .iter()
+        .map(|tag_meta| {
+            vec![
+                match kind {
+                    TokenKind::Tag => format!("@{}", tag_meta.tag),
+                    _ => tag_meta.tag.to_string(),
+                },
+                rounding::format(
+                    tag_meta.duration.num_minutes(),
+                    rounded_minutes(&tag_meta.tag),
+                    side_by_side,
+                ),
+                format!("{:.1}%", tag_metas.share(tag_meta)),
+                tag_meta.count.to_string(),
+            ]
+        })
+        .collect();
+    // only if the rounding applies to the tokens of the table
+    let rounded_total = tag_metas
+        .iter()
+        .any(|tag_meta| rounded_minutes(&tag_meta.tag).is_some())
+        .then(|| {
+            tag_metas
+                .iter()
+                .map(|tag_meta| {
+                    rounded_minutes(&tag_meta.tag).unwrap_or(tag_meta.duration.num_minutes())
+                })
+                .sum()
+        });
+    let mut footer = vec![vec![
+        "Total".to_string(),
+        rounding::format(
+            tag_metas.duration().num_minutes(),
+            rounded_total,
+            side_by_side,
+        ),
+    ]];
+    // entries with several tokens count towards each of them in full
+    let overlap = tag_metas.overlap();
+    if overlap.num_minutes() > 0 {
+        footer.push(vec!["Logged".to_string(), tag_metas.logged.to_string()]);
+        footer.push(vec!["Overlap".to_string(), overlap.to_string()]);
+    }
+    Table {
+        title,
+        headers: vec![title, "Duration", "Share", "Count"],
+        numeric_from: 1,
+        rows,
+        footer,
+    }
+}
+
+/// Duration and share of each configured project, longest first
+fn project_table(config: &Config, log_days: &LogDays) -> Table {
+    let mut minutes: HashMap<String, i64> = HashMap::new();
+    for entry in log_days.iter().flat_map(|day| day.iter()) {
+        let project = config
+            .project_for(entry.description())
+            .map(|project| project.name.to_string())
+            .unwrap_or_else(|| "no project".to_string());
+        *minutes.entry(project).or_insert(0) += entry.time_range().duration().num_minutes();
+    }
+    let total = log_days.duration_total().num_minutes();
+    let rows = minutes
+        .into_iter()
+        .sorted_by(|(a, a_minutes), (b, b_minutes)| b_minutes.cmp(a_minutes).then(a.cmp(b)))
+        .map(|(project, minutes)| {
+            let share = match total {
+                0 => 0.0,
+                _ => minutes as f64 / total as f64 * 100.0,
+            };
+            vec![
+                project,
+                LogDuration::from_minutes(minutes).to_string(),
+                format!("{:.1}%", share),
+            ]
+        })
+        .collect();
+    Table {
+        title: "Projects",
+        headers: vec!["Projects", "Duration", "Share"],
+        numeric_from: 1,
+        rows,
+        footer: vec![vec![
+            "Total".to_string(),
+            LogDuration::from_minutes(total).to_string(),
+        ]],
+    }
+}
+
+/// Time logged and the average day by weekday
+fn weekday_table(log_days: &LogDays, schedule: &Schedule) -> Table {
+    let averages = schedule.average_by_weekday(log_days);
+    let rows = log_days
+        .minutes_by_weekday()
+        .into_iter()
+        .zip(averages)
+        .map(|((day, minutes), average)| {
+            vec![
+                day.to_string(),
+                LogDuration::from_minutes(minutes as i64).to_string(),
+                average
+                    .map(|minutes| LogDuration::from_minutes(minutes).to_string())
+                    .unwrap_or_default(),
+            ]
+        })
+        .collect();
+    Table {
+        title: "Weekdays",
+        headers: vec!["Day", "Hours", "Average"],
+        numeric_from: 1,
+        rows,
+        footer: vec![],
+    }
+}
+
+/// Days of each kind off and the hours they would have been worked, none
+/// without any day off
+fn time_off_table(schedule: &Schedule, (start, end): (NaiveDate, NaiveDate)) -> Option<Table> {
+    let time_off = schedule.time_off(start, end);
+    if time_off.is_empty() {
+        return None;
+    }
+    let rows = time_off
+        .into_iter()
+        .map(|(off, days, minutes)| {
+            vec![
+                off.to_string(),
+                days.to_string(),
+                LogDuration::from_minutes(minutes).to_string(),
+            ]
+        })
+        .collect();
+    Some(Table {
+        title: "Days off",
+        headers: vec!["Days off", "Days", "Hours"],
+        numeric_from: 1,
+        rows,
+        footer: vec![],
+    })
+}
+
+/// Date, description and duration of each day, as in the List tab
+fn list_table(config: &Config, log_days: &LogDays, schedule: &Schedule) -> Table {
+    let rounding = config.rounding.as_ref();
+    let side_by_side = rounding.is_some_and(|rounding| rounding.side_by_side);
+    let rows = log_days
+        .iter()
+        .map(|day| {
+            let mut description = description(&day.description());
+            if let Some(off) = schedule.day_off(day.date().naive_date()) {
+                description = format!("{} {}", off, description).trim_end().to_string();
+            }
+            vec![
+                day.date().to_compact_string(),
+                description,
+                rounding::format(
+                    day.duration_total().num_minutes(),
+                    rounding.and_then(|rounding| rounding::day_total(rounding, day)),
+                    side_by_side,
+                ),
+            ]
+        })
+        .collect();
+    Table {
+        title: "List",
+        headers: vec!["Date", "Description", "Duration"],
+        numeric_from: 2,
+        rows,
+        footer: vec![vec![
+            "Total".to_string(),
+            String::new(),
+            rounding::format(
+                log_days.duration_total().num_minutes(),
+                rounding.map(|rounding| rounding::total(rounding, log_days)),
+                side_by_side,
+            ),
+        ]],
+    }
+}
+
+/// Tags, tickets and prose of a day as plain text
+fn description(tokens: &Tokens) -> String {
+    tokens
+        .to_vec()
+        .iter()
+        .map(|token: &Token| match token.kind {
+            TokenKind::Tag => format!("@{}", token),
+            _ => token.to_string(),
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Cells of a footer row, with empty cells for the missing columns
+fn padded(cells: &[String], columns: usize) -> impl Iterator<Item = &String> {
+    const EMPTY: &String = &String::new();
+    cells
+        .iter()
+        .chain(std::iter::repeat(EMPTY))
+        .take(columns.max(cells.len()))
+}
+
+/// Signed duration, e.g. `+2h15m`
+fn signed(minutes: i64) -> String {
+    let duration = LogDuration::from_minutes(minutes.abs());
+    match minutes {
+        0 => duration.to_string(),
+        _ if minutes > 0 => format!("+{}", duration),
+        _ => format!("-{}", duration),
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{app::config::Project, parser::timesheet::parse_entry};
+
+    use super::*;
+
+    fn report() -> Report {
+        let config = Config {
+            daily_target: 8.0,
+            projects: vec![Project {
+                name: "Acme".to_string(),
+                ticket_prefix: "EXAMPLE-".to_string(),
+                tags: vec![],
+                rate: None,
+                recipient: None,
+            }],
+            ..Config::empty()
+        };
+        let timesheet = "2026-01-05\n\
+            09:00-11:00 EXAMPLE-1 @review pairing | <b>\n\
+            11:00-12:00 @meeting\n\
+            \n\
+            2026-01-06\n\
+            09:00-10:30 EXAMPLE-2\n";
+        let entries = parse_entry(timesheet, &config).unwrap().1.entries;
+        let today = NaiveDate::from_ymd(2026, 1, 6);
+        let log_days = LogDays::from_entries(today.and_hms(18, 0, 0), entries);
+        Report::new(
+            &config,
+            &log_days,
+            (
+                NaiveDate::from_ymd(2026, 1, 5),
+                NaiveDate::from_ymd(2026, 1, 12),
+            ),
+            today,
+        )
+    }
+
+    #[test]
+    fn renders_markdown_tables() {
+        let markdown = report().to_markdown();
+        assert!(
+            markdown.starts_with("# Report 2026-01-05 to 2026-01-11\n\n4h30m / 16h0m -11h30m\n")
+        );
+        assert!(markdown.contains(
+            "## Tickets\n\n\
+            | Tickets | Duration | Share | Count |\n\
+            | --- | ---: | ---: | ---: |\n\
+            | EXAMPLE-1 | 2h0m | 57.1% | 1 |\n\
+            | EXAMPLE-2 | 1h30m | 42.9% | 1 |\n\
+            | **Total** | 3h30m |  |  |\n"
+        ));
+        assert!(markdown.contains(
+            "| Projects | Duration | Share |\n\
+            | --- | ---: | ---: |\n\
+            | Acme | 3h30m | 77.8% |\n\
+            | no project | 1h0m | 22.2% |\n"
+        ));
+        assert!(markdown.contains("| Mon | 3h0m | 3h0m |\n"));
+        assert!(markdown
+            .contains("| 05/01/2026 | @review @meeting EXAMPLE-1 pairing \\| <b> | 3h0m |\n"));
+        assert!(markdown.ends_with("| **Total** |  | 4h30m |\n"));
+    }
+
+    #[test]
+    fn reports_leave_on_its_own() {
+        let config = Config::empty();
+        let timesheet = "2026-01-05\n\
+            09:00-17:00 @acme\n\
+            \n\
+            2026-01-06 leave\n\
+            \n\
+            2026-01-07 leave\n";
+        let entries = parse_entry(timesheet, &config).unwrap().1.entries;
+        let today = NaiveDate::from_ymd(2026, 1, 9);
+        let log_days = LogDays::from_entries(today.and_hms(18, 0, 0), entries);
+        let week = (
+            NaiveDate::from_ymd(2026, 1, 5),
+            NaiveDate::from_ymd(2026, 1, 12),
+        );
+        let markdown = Report::new(&config, &log_days, week, today).to_markdown();
+        assert!(markdown.contains(
+            "## Days off\n\n\
+            | Days off | Days | Hours |\n\
+            | --- | ---: | ---: |\n\
+            | leave | 2 | 16h0m |\n"
+        ));
+
+        let next_week = (
+            NaiveDate::from_ymd(2026, 1, 12),
+            NaiveDate::from_ymd(2026, 1, 19),
+        );
+        let markdown = Report::new(&config, &log_days, next_week, today).to_markdown();
+        assert!(!markdown.contains("## Days off"));
+    }
+
+    #[test]
+    fn renders_self_contained_html() {
+        let html = report().to_html();
+        assert!(html.contains("<style>"));
+        assert!(!html.contains("<link"));
+        assert!(html.contains("pairing | &lt;b&gt;"));
+        assert!(html.contains("<td class=\"numeric\">3h0m</td>"));
+        // one bar for each weekday, the busiest one at full height
+        assert_eq!(7, html.matches("<rect ").count());
+        assert!(html.contains(
+            "<rect x=\"16\" y=\"20\" width=\"40\" height=\"120\"><title>3h0m</title></rect>"
+        ));
+        assert!(html.contains(
+            "<rect x=\"72\" y=\"80\" width=\"40\" height=\"60\"><title>1h30m</title></rect>"
+        ));
+    }
+}
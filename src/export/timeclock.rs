@@ -0,0 +1,127 @@
+use crate::{
+    app::config::Config,
+    model::model::{LogDays, LogEntry},
+    parser::token::TokenKind,
+};
+
+const FORMAT: &str = "%Y/%m/%d %H:%M:%S";
+
+/// Entries as hledger timeclock `i` and `o` pairs, e.g.
+/// `i 2026/01/05 09:00:00 acme:EXAMPLE-1  pairing`. The ongoing entry is
+/// only clocked in and entries without a duration are commented out.
+pub fn timeclock(config: &Config, log_days: &LogDays) -> String {
+    let mut timeclock = String::new();
+    for day in log_days.iter() {
+        let date = day.date().naive_date();
+        for entry in day.iter() {
+            let (start, end) = entry.time_range().date_times(date);
+            let clock_in = format!(
+                "i {} {}  {}",
+                start.format(FORMAT),
+                account(config, entry),
+                entry
+                    .description()
+                    .by_kind(TokenKind::Prose)
+                    .to_string()
+                    .trim()
+            );
+            let clock_in = clock_in.trim_end();
+            if entry.time_range().ongoing {
+                timeclock.push_str(&format!("{}\n", clock_in));
+                continue;
+            }
+            if start == end {
+                timeclock.push_str(&format!("; no duration: {}\n", clock_in));
+                continue;
+            }
+            timeclock.push_str(&format!("{}\no {}\n", clock_in, end.format(FORMAT)));
+        }
+    }
+    timeclock
+}
+
+/// Account of the entry from the configured template
+fn account(config: &Config, entry: &LogEntry) -> String {
+    let first = |kind| {
+        entry
+            .description()
+            .by_kind_refs(kind)
+            .first()
+            .map(|token| token.text().to_string())
+            .unwrap_or_default()
+    };
+    let project = config
+        .project_for(entry.description())
+        .map(|project| project.name.to_string())
+        .unwrap_or_default();
+    let account = config
+        .timeclock
+        .account
+        .replace("{project}", &project)
+        .replace("{ticket}", &first(TokenKind::Ticket))
+        .replace("{tag}", &first(TokenKind::Tag));
+    // two spaces would end the account
+    let segments: Vec<String> = account
+        .split(':')
+        .map(|segment| segment.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    match segments.is_empty() {
+        true => config.timeclock.fallback.to_string(),
+        false => segments.join(":"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use crate::{
+        app::config::{Project, Timeclock},
+        parser::timesheet::parse_entry,
+    };
+
+    use super::*;
+
+    #[test]
+    fn clocks_in_and_out() {
+        let config = Config {
+            timeclock: Timeclock {
+                account: "{project}:{ticket}:{tag}".to_string(),
+                fallback: "misc".to_string(),
+            },
+            projects: vec![Project {
+                name: "Acme  Corp".to_string(),
+                ticket_prefix: "EXAMPLE-".to_string(),
+                tags: vec!["acme".to_string()],
+                rate: None,
+                recipient: None,
+            }],
+            ..Config::empty()
+        };
+        let timesheet = "2026-01-05\n\
+            09:00 EXAMPLE-1 @review pairing on it\n\
+            10:00 @acme\n\
+            23:00-01:00 deploy\n\
+            \n\
+            2026-01-06\n\
+            09:00 forgot to stop\n\
+            \n\
+            2026-01-07\n\
+            09:00 @acme working\n";
+        let entries = parse_entry(timesheet, &config).unwrap().1.entries;
+        let now = NaiveDate::from_ymd(2026, 1, 7).and_hms(11, 30, 0);
+        let log_days = LogDays::from_entries(now, entries);
+        assert_eq!(
+            "i 2026/01/05 09:00:00 Acme Corp:EXAMPLE-1:review  pairing on it\n\
+            o 2026/01/05 10:00:00\n\
+            i 2026/01/05 10:00:00 Acme Corp:acme\n\
+            o 2026/01/05 23:00:00\n\
+            i 2026/01/05 23:00:00 misc  deploy\n\
+            o 2026/01/06 01:00:00\n\
+            ; no duration: i 2026/01/06 09:00:00 misc  forgot to stop\n\
+            i 2026/01/07 09:00:00 Acme Corp:acme  working\n",
+            timeclock(&config, &log_days)
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, NaiveTime, Timelike};
+
+/// Line of a merged timesheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub text: String,
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Existing,
+    Added,
+    /// Not written, for the reason given
+    LeftOut(String),
+}
+
+impl Line {
+    fn existing(text: &str) -> Line {
+        Line {
+            text: text.to_string(),
+            change: Change::Existing,
+        }
+    }
+
+    fn added(text: &str) -> Line {
+        Line {
+            text: text.to_string(),
+            change: Change::Added,
+        }
+    }
+
+    pub fn is_added(&self) -> bool {
+        self.change == Change::Added
+    }
+
+    fn is_written(&self) -> bool {
+        !matches!(self.change, Change::LeftOut(_))
+    }
+}
+
+/// Date header and the entries following it
+struct Block {
+    date: NaiveDate,
+    header: Line,
+    logs: Vec<Line>,
+    /// Blank or other lines until the next date
+    trailing: Vec<Line>,
+}
+
+/// Merge entries by date into a timesheet. Entries are added to the day of
+/// their date if there already is one, in order of their start, and days
+/// are added in order of their date. Entries starting with an entry of the
+/// day are left out, so that importing again adds nothing. Entries which
+/// overlap an entry of the day, or would end an entry without an end
+/// earlier, are left out with the reason.
+pub fn merge(timesheet: &str, days: &BTreeMap<NaiveDate, Vec<String>>) -> Vec<Line> {
+    let mut preamble = vec![];
+    let mut blocks: Vec<Block> = vec![];
+    for text in timesheet.lines() {
+        if let Some(date) = date(text) {
+            blocks.push(Block {
+                date,
+                header: Line::existing(text),
+                logs: vec![],
+                trailing: vec![],
+            });
+            continue;
+        }
+        match blocks.last_mut() {
+            Some(block) if block.trailing.is_empty() && !text.trim().is_empty() => {
+                block.logs.push(Line::existing(text))
+            }
+            Some(block) => block.trailing.push(Line::existing(text)),
+            None => preamble.push(Line::existing(text)),
+        }
+    }
+
+    for (date, lines) in days {
+        let index = match blocks.iter().position(|block| block.date == *date) {
+            Some(index) => index,
+            None => {
+                let index = blocks
+                    .iter()
+                    .position(|block| block.date > *date)
+                    .unwrap_or(blocks.len());
+                if index == blocks.len() {
+                    if let Some(last) = blocks.last_mut() {
+                        if last.trailing.is_empty() {
+                            last.trailing.push(Line::added(""));
+                        }
+                    }
+                }
+                let trailing = match index == blocks.len() {
+                    true => vec![],
+                    false => vec![Line::added("")],
+                };
+                blocks.insert(
+                    index,
+                    Block {
+                        date: *date,
+                        header: Line::added(&date.to_string()),
+                        logs: vec![],
+                        trailing,
+                    },
+                );
+                index
+            }
+        };
+        let logs = &mut blocks[index].logs;
+        for line in lines {
+            let change = match conflict(logs, line) {
+                Conflict::Starts => continue,
+                Conflict::None => Change::Added,
+                Conflict::Overlaps(log) => Change::LeftOut(format!("overlaps {}", log)),
+                Conflict::Ends(log) => Change::LeftOut(format!(
+                    "would end {} at {}",
+                    log,
+                    line.get(0..5).unwrap_or(line)
+                )),
+            };
+            let start = times(line).map(|(start, _)| start);
+            let position = logs
+                .iter()
+                .position(|log| times(&log.text).map(|(start, _)| start) > start)
+                .unwrap_or(logs.len());
+            logs.insert(
+                position,
+                Line {
+                    text: line.to_string(),
+                    change,
+                },
+            );
+        }
+    }
+
+    let mut merged = preamble;
+    for block in blocks {
+        merged.push(block.header);
+        merged.extend(block.logs);
+        merged.extend(block.trailing);
+    }
+    merged
+}
+
+/// The merged timesheet
+pub fn to_string(lines: &[Line]) -> String {
+    lines
+        .iter()
+        .filter(|line| line.is_written())
+        .map(|line| format!("{}\n", line.text))
+        .collect()
+}
+
+/// The lines added to, or left out of, each day, after the date of the day
+pub fn diff(lines: &[Line]) -> String {
+    let mut diff = String::new();
+    let mut header: Option<&Line> = None;
+    for line in lines {
+        if date(&line.text).is_some() {
+            header = Some(line);
+            if line.is_added() {
+                diff.push_str(&format!("+{}\n", line.text));
+            }
+            continue;
+        }
+        if line.change == Change::Existing || line.text.is_empty() {
+            continue;
+        }
+        if let Some(header) = header.take().filter(|header| !header.is_added()) {
+            diff.push_str(&format!(" {}\n", header.text));
+        }
+        match &line.change {
+            Change::LeftOut(reason) => diff.push_str(&format!("!{}  ({})\n", line.text, reason)),
+            _ => diff.push_str(&format!("+{}\n", line.text)),
+        }
+    }
+    diff
+}
+
+/// How an imported entry conflicts with the entries of its day
+enum Conflict<'a> {
+    None,
+    /// An entry starts at the same time, it was imported before
+    Starts,
+    Overlaps(&'a str),
+    /// The entry before has no end, so it lasts until the next entry
+    Ends(&'a str),
+}
+
+fn conflict<'a>(logs: &'a [Line], line: &str) -> Conflict<'a> {
+    let (start, end) = match times(line) {
+        Some(times) => times,
+        None => return Conflict::None,
+    };
+    let logs: Vec<(&str, (u32, Option<u32>))> = logs
+        .iter()
+        .filter(|log| log.is_written())
+        .filter_map(|log| times(&log.text).map(|times| (log.text.as_str(), times)))
+        .collect();
+    if logs.iter().any(|(_, (log_start, _))| *log_start == start) {
+        return Conflict::Starts;
+    }
+    let before = logs
+        .iter()
+        .filter(|(_, (log_start, _))| *log_start < start)
+        .max_by_key(|(_, (log_start, _))| *log_start);
+    if let Some((log, (_, None))) = before {
+        return Conflict::Ends(log);
+    }
+    // without an end, the entry lasts until the next one
+    let end = end.unwrap_or(start + 1);
+    let overlapping = logs.iter().find(|(_, (log_start, log_end))| {
+        *log_start < end && start < log_end.unwrap_or(*log_start + 1)
+    });
+    match overlapping {
+        Some((log, _)) => Conflict::Overlaps(log),
+        None => Conflict::None,
+    }
+}
+
+fn date(text: &str) -> Option<NaiveDate> {
+    text.get(0..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
+
+/// Minutes into the day of the start and end of an entry, an end before the
+/// start is on the next day
+fn times(text: &str) -> Option<(u32, Option<u32>)> {
+    let range = text.split_whitespace().next()?;
+    let minutes = |time| {
+        NaiveTime::parse_from_str(time, "%H:%M")
+            .ok()
+            .map(|time| time.hour() * 60 + time.minute())
+    };
+    match range.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (minutes(start)?, minutes(end)?);
+            Some((start, Some(if end < start { end + 24 * 60 } else { end })))
+        }
+        None => Some((minutes(range)?, None)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn days(days: &[(u32, &[&str])]) -> BTreeMap<NaiveDate, Vec<String>> {
+        days.iter()
+            .map(|(day, lines)| {
+                (
+                    NaiveDate::from_ymd(2026, 1, *day),
+                    lines.iter().map(|line| line.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    const TIMESHEET: &str = "2026-01-05\n\
+        09:00-09:15 @acme standup\n\
+        11:00-12:00 @acme review\n\
+        \n\
+        2026-01-07\n\
+        09:00-10:00 @acme planning\n";
+
+    #[test]
+    fn merges_into_existing_days_in_order() {
+        let merged = merge(
+            TIMESHEET,
+            &days(&[
+                (
+                    5,
+                    &[
+                        "10:00-11:00 @acme pairing",
+                        "11:00-12:00 @acme review again",
+                    ],
+                ),
+                (6, &["08:00-09:00 EX-1"]),
+                (8, &["13:00-14:00 EX-2"]),
+            ]),
+        );
+        assert_eq!(
+            "2026-01-05\n\
+            09:00-09:15 @acme standup\n\
+            10:00-11:00 @acme pairing\n\
+            11:00-12:00 @acme review\n\
+            \n\
+            2026-01-06\n\
+            08:00-09:00 EX-1\n\
+            \n\
+            2026-01-07\n\
+            09:00-10:00 @acme planning\n\
+            \n\
+            2026-01-08\n\
+            13:00-14:00 EX-2\n",
+            to_string(&merged)
+        );
+        assert_eq!(
+            " 2026-01-05\n\
+            +10:00-11:00 @acme pairing\n\
+            +2026-01-06\n\
+            +08:00-09:00 EX-1\n\
+            +2026-01-08\n\
+            +13:00-14:00 EX-2\n",
+            diff(&merged)
+        );
+    }
+
+    #[test]
+    fn merging_again_adds_nothing() {
+        let days = days(&[
+            (5, &["10:00-11:00 @acme pairing"]),
+            (6, &["08:00-09:00 EX-1"]),
+        ]);
+        let merged = to_string(&merge(TIMESHEET, &days));
+        let again = merge(&merged, &days);
+        assert_eq!("", diff(&again));
+        assert_eq!(merged, to_string(&again));
+    }
+
+    #[test]
+    fn leaves_out_conflicting_entries() {
+        let timesheet = "2026-01-05\n\
+            09:00 @acme standup\n\
+            11:00-12:00 @acme review\n";
+        let merged = merge(
+            timesheet,
+            &days(&[(
+                5,
+                &[
+                    "09:00-09:15 EX-1 standup",
+                    "09:30-09:45 EX-2",
+                    "11:30-12:30 EX-3",
+                    "12:00-13:00 EX-4",
+                ],
+            )]),
+        );
+        assert_eq!(
+            "2026-01-05\n\
+            09:00 @acme standup\n\
+            11:00-12:00 @acme review\n\
+            12:00-13:00 EX-4\n",
+            to_string(&merged)
+        );
+        assert_eq!(
+            " 2026-01-05\n\
+            !09:30-09:45 EX-2  (would end 09:00 @acme standup at 09:30)\n\
+            !11:30-12:30 EX-3  (overlaps 11:00-12:00 @acme review)\n\
+            +12:00-13:00 EX-4\n",
+            diff(&merged)
+        );
+    }
+
+    #[test]
+    fn times_roll_over_midnight() {
+        assert_eq!(Some((9 * 60, None)), times("09:00 standup"));
+        assert_eq!(Some((23 * 60, Some(25 * 60))), times("23:00-01:00 release"));
+        assert_eq!(None, times("notes"));
+    }
+
+    #[test]
+    fn merges_into_empty_timesheet() {
+        let merged = merge(
+            "",
+            &days(&[(5, &["10:00-11:00 EX-1"]), (6, &["08:00 EX-2"])]),
+        );
+        assert_eq!(
+            "2026-01-05\n10:00-11:00 EX-1\n\n2026-01-06\n08:00 EX-2\n",
+            to_string(&merged)
+        );
+    }
+}
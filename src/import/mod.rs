@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::app::config::Config;
+
+pub mod merge;
+pub mod org;
+pub mod timewarrior;
+pub mod tracker_csv;
+
+/// Time clocked in another tool
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clocked {
+    pub start: NaiveDateTime,
+    /// None while still running
+    pub end: Option<NaiveDateTime>,
+    pub tags: Vec<String>,
+    pub tickets: Vec<String>,
+    pub prose: String,
+}
+
+impl Clocked {
+    /// Entry in the timesheet, e.g. `09:00-10:30 @acme EXAMPLE-1 pairing`.
+    /// An end on a later day rolls over midnight.
+    pub fn to_line(&self) -> String {
+        let mut parts = vec![match self.end {
+            Some(end) => format!("{}-{}", self.start.format("%H:%M"), end.format("%H:%M")),
+            None => self.start.format("%H:%M").to_string(),
+        }];
+        parts.extend(self.tags.iter().map(|tag| format!("@{}", tag)));
+        parts.extend(self.tickets.iter().cloned());
+        if !self.prose.trim().is_empty() {
+            parts.push(self.prose.trim().to_string());
+        }
+        parts.join(" ")
+    }
+}
+
+/// Split tags of another tool into tickets, for the tags starting with the
+/// prefix of a configured project, and tags with only the alphanumeric
+/// characters a tag may have
+pub fn tags_and_tickets(names: &[String], config: &Config) -> (Vec<String>, Vec<String>) {
+    let mut tags = vec![];
+    let mut tickets = vec![];
+    for name in names {
+        let is_ticket = config.projects.iter().any(|project| {
+            !project.ticket_prefix.is_empty() && name.starts_with(&project.ticket_prefix)
+        });
+        if is_ticket {
+            tickets.push(name.to_string());
+            continue;
+        }
+        let tag: String = name.chars().filter(|c| c.is_alphanumeric()).collect();
+        if !tag.is_empty() {
+            tags.push(tag);
+        }
+    }
+    (tags, tickets)
+}
+
+/// Timesheet lines by date, in order of their start and leaving out
+/// entries of less than a minute
+pub fn by_date(clocked: &[Clocked]) -> BTreeMap<NaiveDate, Vec<String>> {
+    let mut clocked: Vec<&Clocked> = clocked
+        .iter()
+        .filter(|clocked| {
+            clocked
+                .end
+                .is_none_or(|end| (end - clocked.start).num_minutes() > 0)
+        })
+        .collect();
+    clocked.sort_by_key(|clocked| clocked.start);
+    let mut days: BTreeMap<NaiveDate, Vec<String>> = BTreeMap::new();
+    for clocked in clocked {
+        days.entry(clocked.start.date())
+            .or_default()
+            .push(clocked.to_line());
+    }
+    days
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::config::Project;
+
+    use super::*;
+
+    #[test]
+    fn renders_lines_by_date() {
+        let at = |day, h, m| NaiveDate::from_ymd(2026, 1, day).and_hms(h, m, 0);
+        let clocked = |start, end, prose: &str| Clocked {
+            start,
+            end,
+            tags: vec!["acme".to_string()],
+            tickets: vec!["EX-1".to_string()],
+            prose: prose.to_string(),
+        };
+        let days = by_date(&[
+            clocked(at(5, 23, 0), Some(at(6, 1, 30)), "release"),
+            clocked(at(5, 9, 0), Some(at(5, 9, 0)), "blip"),
+            clocked(at(5, 8, 15), Some(at(5, 9, 0)), ""),
+            clocked(at(6, 10, 0), None, "ongoing"),
+        ]);
+        assert_eq!(
+            BTreeMap::from([
+                (
+                    NaiveDate::from_ymd(2026, 1, 5),
+                    vec![
+                        "08:15-09:00 @acme EX-1".to_string(),
+                        "23:00-01:30 @acme EX-1 release".to_string()
+                    ]
+                ),
+                (
+                    NaiveDate::from_ymd(2026, 1, 6),
+                    vec!["10:00 @acme EX-1 ongoing".to_string()]
+                ),
+            ]),
+            days
+        );
+    }
+
+    #[test]
+    fn splits_tags_and_tickets() {
+        let config = Config {
+            projects: vec![Project {
+                name: "example".to_string(),
+                ticket_prefix: "EX-".to_string(),
+                tags: vec![],
+                rate: None,
+                recipient: None,
+            }],
+            ..Config::empty()
+        };
+        let names = ["EX-12", "client work", "@"].map(String::from);
+        assert_eq!(
+            (vec!["clientwork".to_string()], vec!["EX-12".to_string()]),
+            tags_and_tickets(&names, &config)
+        );
+    }
+}
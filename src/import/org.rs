@@ -0,0 +1,175 @@
+use anyhow::{bail, Result};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::app::config::Config;
+
+use super::{tags_and_tickets, Clocked};
+
+const KEYWORDS: [&str; 7] = [
+    "TODO",
+    "NEXT",
+    "WAITING",
+    "HOLD",
+    "DONE",
+    "CANCELLED",
+    "CANCELED",
+];
+
+/// Heading of an org file, e.g. `** TODO [#A] EX-1 write docs :acme:`
+struct Heading {
+    level: usize,
+    title: String,
+    tags: Vec<String>,
+}
+
+/// Read the `CLOCK:` lines of an org file, e.g.
+/// `CLOCK: [2026-01-05 Mon 09:00]--[2026-01-05 Mon 10:30] =>  1:30`. The
+/// heading a clock is under gives its tickets and prose and, along with the
+/// headings above it, its tags.
+pub fn read(text: &str, config: &Config) -> Result<Vec<Clocked>> {
+    let mut headings: Vec<Heading> = vec![];
+    let mut clocked = vec![];
+    for (number, line) in text.lines().enumerate() {
+        if let Some(heading) = heading(line) {
+            headings.retain(|parent| parent.level < heading.level);
+            headings.push(heading);
+            continue;
+        }
+        let clock = match line.trim().strip_prefix("CLOCK:") {
+            Some(clock) => clock.trim(),
+            None => continue,
+        };
+        let (start, end) = match clock.split_once("--") {
+            Some((start, end)) => (start, Some(end.split("=>").next().unwrap_or(end))),
+            None => (clock, None),
+        };
+        let (start, end) = match (timestamp(start), end.map(timestamp)) {
+            (Some(start), None) => (start, None),
+            (Some(start), Some(Some(end))) => (start, Some(end)),
+            _ => bail!("line {}: invalid clock \"{}\"", number + 1, line.trim()),
+        };
+
+        let names: Vec<String> = headings
+            .iter()
+            .flat_map(|heading| heading.tags.clone())
+            .collect();
+        let (tags, mut tickets) = tags_and_tickets(&names, config);
+        let mut prose = vec![];
+        for word in headings
+            .last()
+            .map_or("", |heading| heading.title.as_str())
+            .split_whitespace()
+        {
+            match tags_and_tickets(&[word.to_string()], config) {
+                (_, ticket) if !ticket.is_empty() => tickets.extend(ticket),
+                _ => prose.push(word),
+            }
+        }
+        clocked.push(Clocked {
+            start,
+            end,
+            tags,
+            tickets,
+            prose: prose.join(" "),
+        });
+    }
+    Ok(clocked)
+}
+
+fn heading(line: &str) -> Option<Heading> {
+    let level = line.chars().take_while(|c| *c == '*').count();
+    if level == 0 || !line[level..].starts_with(' ') {
+        return None;
+    }
+    let mut words: Vec<&str> = line[level..].split_whitespace().collect();
+    let tags = match words.last() {
+        Some(last) if last.len() > 1 && last.starts_with(':') && last.ends_with(':') => {
+            let tags = last
+                .trim_matches(':')
+                .split(':')
+                .map(String::from)
+                .collect();
+            words.pop();
+            tags
+        }
+        _ => vec![],
+    };
+    if words.first().is_some_and(|word| KEYWORDS.contains(word)) {
+        words.remove(0);
+    }
+    if words
+        .first()
+        .is_some_and(|word| word.starts_with("[#") && word.ends_with(']'))
+    {
+        words.remove(0);
+    }
+    Some(Heading {
+        level,
+        title: words.join(" "),
+        tags,
+    })
+}
+
+/// Inactive timestamp, e.g. `[2026-01-05 Mon 09:00]`
+fn timestamp(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let mut parts = text.split_whitespace();
+    let date = NaiveDate::parse_from_str(parts.next()?, "%Y-%m-%d").ok()?;
+    let time = NaiveTime::parse_from_str(parts.last()?, "%H:%M").ok()?;
+    Some(date.and_time(time))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::config::Project;
+
+    use super::*;
+
+    #[test]
+    fn reads_clocks_under_headings() {
+        let config = Config {
+            projects: vec![Project {
+                name: "example".to_string(),
+                ticket_prefix: "EX-".to_string(),
+                tags: vec![],
+                rate: None,
+                recipient: None,
+            }],
+            ..Config::empty()
+        };
+        let org = "* Work :acme:\n\
+            ** TODO [#A] EX-1 write the docs :writing:\n\
+            :LOGBOOK:\n\
+            CLOCK: [2026-01-05 Mon 09:00]--[2026-01-05 Mon 10:30] =>  1:30\n\
+            CLOCK: [2026-01-06 Tue 14:00]\n\
+            :END:\n\
+            * Personal\n\
+            CLOCK: [2026-01-05 Mon 12:00]--[2026-01-05 Mon 12:30] =>  0:30\n";
+        let lines: Vec<String> = read(org, &config)
+            .unwrap()
+            .iter()
+            .map(|clocked| clocked.to_line())
+            .collect();
+        assert_eq!(
+            vec![
+                "09:00-10:30 @acme @writing EX-1 write the docs",
+                "14:00 @acme @writing EX-1 write the docs",
+                "12:00-12:30 Personal",
+            ],
+            lines
+        );
+    }
+
+    #[test]
+    fn reports_invalid_clocks() {
+        let err = read(
+            "* Work\n  CLOCK: [2026-01-05 Mon 09:00]--[soon]\n",
+            &Config::empty(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            "line 2: invalid clock \"CLOCK: [2026-01-05 Mon 09:00]--[soon]\"",
+            err.to_string()
+        );
+    }
+}
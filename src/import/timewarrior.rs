@@ -0,0 +1,132 @@
+use anyhow::{bail, Error, Result};
+use chrono::{NaiveDateTime, TimeZone, Utc};
+
+use crate::app::config::Config;
+
+use super::{tags_and_tickets, Clocked};
+
+/// Read the intervals of a Timewarrior data file, e.g.
+/// `inc 20260105T080000Z - 20260105T093000Z # acme EX-1 "code review" # "annotation"`.
+/// Times are stored in UTC and converted to the time zone.
+pub fn read<Tz: TimeZone>(text: &str, config: &Config, tz: &Tz) -> Result<Vec<Clocked>> {
+    let mut clocked = vec![];
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let interval = match line.strip_prefix("inc ") {
+            Some(interval) => interval,
+            None => bail!(
+                "line {}: expected an interval starting with \"inc\"",
+                number + 1
+            ),
+        };
+        let (range, rest) = match interval.split_once(" # ") {
+            Some((range, rest)) => (range, Some(rest)),
+            None => (interval.trim_end_matches(" #"), None),
+        };
+        let (tags, annotation) = match rest.and_then(|rest| rest.split_once(" # ")) {
+            Some((tags, annotation)) => (tags, words(annotation).join(" ")),
+            None => (rest.unwrap_or(""), String::new()),
+        };
+        let time = |text: &str| {
+            NaiveDateTime::parse_from_str(text.trim(), "%Y%m%dT%H%M%SZ")
+                .map(|utc| Utc.from_utc_datetime(&utc).with_timezone(tz).naive_local())
+                .map_err(|_| Error::msg(format!("line {}: invalid time \"{}\"", number + 1, text)))
+        };
+        let (start, end) = match range.split_once(" - ") {
+            Some((start, end)) => (time(start)?, Some(time(end)?)),
+            None => (time(range)?, None),
+        };
+        let (tags, tickets) = tags_and_tickets(&words(tags), config);
+        clocked.push(Clocked {
+            start,
+            end,
+            tags,
+            tickets,
+            prose: annotation,
+        });
+    }
+    Ok(clocked)
+}
+
+/// Words separated by spaces, a quoted word may contain spaces and
+/// escaped quotes
+fn words(text: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut chars = text.trim().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if quoted => word.extend(chars.next()),
+            '"' => quoted = !quoted,
+            ' ' if !quoted => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{FixedOffset, NaiveDate};
+
+    use crate::app::config::Project;
+
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            projects: vec![Project {
+                name: "example".to_string(),
+                ticket_prefix: "EX-".to_string(),
+                tags: vec![],
+                rate: None,
+                recipient: None,
+            }],
+            ..Config::empty()
+        }
+    }
+
+    #[test]
+    fn reads_intervals_in_local_time() {
+        let data = "inc 20260105T080000Z - 20260105T093000Z # acme EX-1 \"code review\" # \"the \\\"big\\\" one\"\n\
+            \n\
+            inc 20260105T100000Z - 20260105T101500Z\n\
+            inc 20260105T110000Z # acme\n";
+        let clocked = read(data, &config(), &FixedOffset::east(3600)).unwrap();
+        let at = |h, m| NaiveDate::from_ymd(2026, 1, 5).and_hms(h, m, 0);
+        assert_eq!(
+            Clocked {
+                start: at(9, 0),
+                end: Some(at(10, 30)),
+                tags: vec!["acme".to_string(), "codereview".to_string()],
+                tickets: vec!["EX-1".to_string()],
+                prose: "the \"big\" one".to_string(),
+            },
+            clocked[0]
+        );
+        assert_eq!("11:00-11:15", clocked[1].to_line());
+        assert_eq!("12:00 @acme", clocked[2].to_line());
+    }
+
+    #[test]
+    fn reports_invalid_lines() {
+        let err = read("inc 20260105T080000Z - tomorrow", &config(), &Utc).unwrap_err();
+        assert_eq!("line 1: invalid time \"tomorrow\"", err.to_string());
+        let err = read("\nexc monday", &config(), &Utc).unwrap_err();
+        assert_eq!(
+            "line 2: expected an interval starting with \"inc\"",
+            err.to_string()
+        );
+    }
+}
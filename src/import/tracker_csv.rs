@@ -0,0 +1,123 @@
+use anyhow::{bail, Error, Result};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::app::config::Config;
+
+use super::{tags_and_tickets, Clocked};
+
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%m/%d/%Y", "%d.%m.%Y"];
+const TIME_FORMATS: [&str; 4] = ["%H:%M:%S", "%H:%M", "%I:%M:%S %p", "%I:%M %p"];
+
+/// Read the detailed CSV export of Toggl or Clockify. The project becomes a
+/// tag along with the tags, which may also be tickets, and the description
+/// (or else the task) becomes the prose.
+pub fn read(text: &str, config: &Config) -> Result<Vec<Clocked>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(text.trim_start_matches('\u{feff}').as_bytes());
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|header| header.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| headers.iter().position(|header| header == name);
+    let required = |name: &str| match column(name) {
+        Some(index) => Ok(index),
+        None => Err(Error::msg(format!("missing column \"{}\"", name))),
+    };
+    let (start_date, start_time) = (required("start date")?, required("start time")?);
+    let (end_date, end_time) = (column("end date"), column("end time"));
+    let (description, task) = (column("description"), column("task"));
+    let (project, tags) = (column("project"), column("tags"));
+
+    let mut clocked = vec![];
+    for (number, record) in reader.records().enumerate() {
+        let record = record?;
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .unwrap_or("")
+                .trim()
+        };
+        // the header is the first line
+        let line = number + 2;
+        let start = date_time(field(Some(start_date)), field(Some(start_time)), line)?;
+        let end = match (field(end_date), field(end_time)) {
+            (_, "") => None,
+            ("", time) => Some(date_time(field(Some(start_date)), time, line)?),
+            (date, time) => Some(date_time(date, time, line)?),
+        };
+        let mut names: Vec<String> = vec![field(project).to_string()];
+        names.extend(field(tags).split(',').map(|tag| tag.trim().to_string()));
+        let (tags, tickets) = tags_and_tickets(&names, config);
+        let prose = match field(description) {
+            "" => field(task),
+            description => description,
+        };
+        clocked.push(Clocked {
+            start,
+            end,
+            tags,
+            tickets,
+            prose: prose.to_string(),
+        });
+    }
+    Ok(clocked)
+}
+
+fn date_time(date: &str, time: &str, line: usize) -> Result<NaiveDateTime> {
+    let parsed_date = DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(date, format).ok());
+    let parsed_time = TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(time, format).ok());
+    match (parsed_date, parsed_time) {
+        (Some(date), Some(time)) => Ok(date.and_time(time)),
+        (None, _) => bail!("line {}: invalid date \"{}\"", line, date),
+        (_, None) => bail!("line {}: invalid time \"{}\"", line, time),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reads_toggl_export() {
+        let export = "User,Email,Client,Project,Task,Description,Billable,Start date,Start time,End date,End time,Duration,Tags\n\
+            Jane,jane@example.com,ACME,Warp Core,,fixing the flux,Yes,2026-01-05,09:00:00,2026-01-05,10:30:00,01:30:00,\"meeting, urgent\"\n";
+        let clocked = read(export, &Config::empty()).unwrap();
+        assert_eq!(
+            vec!["09:00-10:30 @WarpCore @meeting @urgent fixing the flux".to_string()],
+            clocked
+                .iter()
+                .map(|clocked| clocked.to_line())
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn reads_clockify_export() {
+        let export = "\"Project\",\"Client\",\"Description\",\"Task\",\"User\",\"Tags\",\"Billable\",\"Start Date\",\"Start Time\",\"End Date\",\"End Time\"\n\
+            \"\",\"\",\"\",\"Planning\",\"Jane\",\"\",\"No\",\"01/05/2026\",\"11:45:00 PM\",\"01/06/2026\",\"12:15:00 AM\"\n";
+        let clocked = read(export, &Config::empty()).unwrap();
+        assert_eq!(
+            NaiveDate::from_ymd(2026, 1, 5).and_hms(23, 45, 0),
+            clocked[0].start
+        );
+        assert_eq!("23:45-00:15 Planning", clocked[0].to_line());
+    }
+
+    #[test]
+    fn reports_missing_columns_and_invalid_dates() {
+        let err = read("Description,Start date\nfoo,2026-01-05\n", &Config::empty()).unwrap_err();
+        assert_eq!("missing column \"start time\"", err.to_string());
+        let err = read(
+            "Start date,Start time\n2026-01-05,09:00\n05 Jan,09:00\n",
+            &Config::empty(),
+        )
+        .unwrap_err();
+        assert_eq!("line 3: invalid date \"05 Jan\"", err.to_string());
+    }
+}
@@ -1,16 +1,23 @@
 pub mod app;
+pub mod cli;
 pub mod component;
+pub mod export;
+pub mod import;
 pub mod model;
 pub mod parser;
 pub mod ui;
 
 use anyhow::Error;
 use anyhow::Result;
-use app::config::map_key_event;
 use app::config::Config;
+use app::history::FilterHistory;
+use app::keymap::KeyMap;
+use app::loader::CommandLoader;
 use app::loader::FileLoader;
+use app::loader::StdinLoader;
 use chrono::Local;
 use clap::Parser;
+use cli::Command;
 use crossterm::event;
 use crossterm::event::poll;
 use crossterm::event::Event;
@@ -24,13 +31,32 @@ use tui::{backend::CrosstermBackend, Terminal};
 
 #[derive(Parser, Debug)]
 #[command(author,version,about,long_about=None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    path: String,
+    /// Timesheets to open, files, directories or globs, or - for stdin
+    #[arg(required_unless_present = "shell_command")]
+    paths: Vec<String>,
+    /// Shell command printing the timesheet, run again on reload
+    #[arg(long = "command", value_name = "COMMAND", conflicts_with = "paths")]
+    shell_command: Option<String>,
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
 fn main() -> Result<(), Error> {
     let args = Args::parse();
-    let path = &args.path;
+
+    let config: Config = confy::load("pttlog", "config").expect("Could not load config");
+    if let Some(command) = args.command {
+        return command.run(&config);
+    }
+    let keymap = KeyMap::new(&config.keys)?;
+    // stdin is read before the terminal is taken over
+    let loader = match args.shell_command {
+        Some(command) => CommandLoader::new(command, &config),
+        None if args.paths == ["-"] => StdinLoader::new(&config)?,
+        None => FileLoader::new(args.paths, &config),
+    };
 
     let mut stdout = io::stdout();
     execute!(stdout)?;
@@ -40,21 +66,21 @@ fn main() -> Result<(), Error> {
     terminal.clear()?;
     let now = Local::now().naive_local();
 
-    let config: Config = confy::load("pttlog", "config").expect("Could not load config");
-    let mut app = app::App::new(
-        FileLoader::new(path.to_string(), &config),
-        &config,
-        &RealTimeFactory {},
-        &now,
-    );
+    let mut app = app::App::new(loader, &config, &RealTimeFactory {}, &now);
+    app.keymap = keymap;
+    app.filter.history = FilterHistory::load().unwrap_or_default();
     app.reload();
 
-    main_loop(&mut terminal, &mut app)?;
+    let result = main_loop(&mut terminal, &mut app);
 
     disable_raw_mode()?;
     execute!(terminal.backend_mut(),)?;
+    // keep the history however the loop ended, without hiding its error
+    if let Err(err) = app.filter.history.store() {
+        eprintln!("could not store the filter history: {}", err);
+    }
 
-    Ok(())
+    result
 }
 
 fn main_loop(
@@ -62,15 +88,28 @@ fn main_loop(
     app: &mut app::App,
 ) -> Result<(), Error> {
     loop {
+        app.reload_changed();
         terminal.draw(|f| app.draw(f).expect("Could not draw"))?;
 
         if (poll(Duration::from_millis(1000)))? {
             if let Event::Key(key) = event::read()? {
-                let key = map_key_event(key);
+                let key = app.keymap.map(key);
                 app.handle(key);
             }
         }
 
+        // the editor takes over the terminal until it exits, the edit is
+        // picked up as any other change to the timesheet
+        if let Some(edit) = app.edit.take() {
+            disable_raw_mode()?;
+            let edited = edit.run();
+            enable_raw_mode()?;
+            terminal.clear()?;
+            if let Err(err) = edited {
+                app.error(err.to_string(), 4);
+            }
+        }
+
         if app.should_quit {
             return Ok(());
         }
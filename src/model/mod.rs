@@ -1,2 +1,5 @@
+#[allow(clippy::module_inception)]
 pub mod model;
+pub mod rounding;
+pub mod target;
 pub mod time;
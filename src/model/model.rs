@@ -1,8 +1,14 @@
+use std::cell::RefCell;
+use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::rc::Rc;
 use std::slice::Iter;
 
+use crate::app::config::Allocation;
 use crate::parser::filter::Filter;
-use crate::parser::timesheet::{Entry, Tokens};
+use crate::parser::timesheet::{DayOff, Entry, Tokens};
 use crate::parser::token::{Token, TokenKind};
 use chrono::{Datelike, Local, Timelike};
 use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
@@ -11,18 +17,54 @@ use itertools::Itertools;
 #[derive(Clone)]
 pub struct LogDays {
     entries: Vec<LogDay>,
+    allocation: Allocation,
 }
 
 impl LogDays {
-    pub fn new<'a>(entries: Vec<Entry>) -> LogDays {
+    pub fn new(entries: Vec<Entry>) -> LogDays {
+        LogDays::from_entries(Local::now().naive_local(), entries)
+    }
+
+    /// Days with the entries of today ongoing until `current_date`
+    pub fn from_entries(current_date: NaiveDateTime, entries: Vec<Entry>) -> LogDays {
         LogDays {
             entries: entries
                 .into_iter()
-                .map(|entry| LogDay::from_entry(Local::now().naive_local(), entry.clone()))
+                .map(|entry| LogDay::from_entry(current_date, entry))
                 .collect(),
+            allocation: Allocation::Full,
         }
     }
 
+    /// How entries with several tags or tickets are attributed in the
+    /// summaries of these days
+    pub fn with_allocation(mut self, allocation: Allocation) -> Self {
+        self.allocation = allocation;
+        self
+    }
+
+    pub fn allocation(&self) -> Allocation {
+        self.allocation
+    }
+
+    /// Keep the summaries made for the `previous` days of the days which
+    /// did not change since, e.g. when the timesheet is reloaded
+    pub fn with_summaries_of(mut self, previous: &LogDays) -> Self {
+        let days: HashMap<NaiveDate, &LogDay> = previous
+            .entries
+            .iter()
+            .map(|day| (day.date.date, day))
+            .collect();
+        for day in self.entries.iter_mut() {
+            if let Some(previous) = days.get(&day.date.date) {
+                if day.is_unchanged_from(previous) {
+                    day.summaries = previous.summaries.clone();
+                }
+            }
+        }
+        self
+    }
+
     pub fn duration_total(&self) -> LogDuration {
         LogDuration {
             duration: Duration::minutes(
@@ -33,7 +75,7 @@ impl LogDays {
         }
     }
 
-    pub fn iter(&self) -> Iter<LogDay> {
+    pub fn iter(&self) -> Iter<'_, LogDay> {
         self.entries.iter()
     }
 
@@ -44,6 +86,30 @@ impl LogDays {
                 .iter()
                 .map(|entry| entry.with_filter(filter))
                 .collect(),
+            allocation: self.allocation,
+        }
+    }
+
+    /// The days with only the entries satisfying the predicate
+    pub(crate) fn retain(&self, predicate: impl Fn(&LogEntry) -> bool) -> Self {
+        LogDays {
+            entries: self
+                .entries
+                .iter()
+                .map(|entry| {
+                    LogDay::with_logs(
+                        entry.date.clone(),
+                        entry.off,
+                        entry
+                            .logs
+                            .iter()
+                            .filter(|log| predicate(log))
+                            .cloned()
+                            .collect(),
+                    )
+                })
+                .collect(),
+            allocation: self.allocation,
         }
     }
 
@@ -56,10 +122,14 @@ impl LogDays {
     }
 
     pub(crate) fn tag_summary(&self, tag: TokenKind) -> TagMetas {
+        let mut logged = 0;
         let entry_map = self.entries.iter().fold(
             HashMap::new(),
             |entry_map: HashMap<String, TagMeta>, view: &LogDay| {
-                view.tag_summary(tag)
+                let day_metas = view.summary(tag, self.allocation);
+                logged += day_metas.logged.num_minutes();
+                day_metas
+                    .tag_metas
                     .iter()
                     .fold(entry_map, |mut entry_map, tag_meta| {
                         let meta = entry_map
@@ -85,8 +155,22 @@ impl LogDays {
         for (_, v) in entry_map {
             tag_metas.push(v)
         }
-        tag_metas.sort_by(|a, b| b.duration.duration.cmp(&a.duration.duration));
-        TagMetas { tag_metas }
+        tag_metas.sort_by_key(|tag_meta| Reverse(tag_meta.duration.duration));
+        TagMetas {
+            tag_metas,
+            logged: LogDuration::from_minutes(logged),
+        }
+    }
+
+    pub(crate) fn logs_with_token(&self, token: &Token) -> Vec<(&LogDate, &LogEntry)> {
+        self.entries
+            .iter()
+            .flat_map(|day| {
+                day.logs_with_token(token)
+                    .into_iter()
+                    .map(move |log| (day.date(), log))
+            })
+            .collect()
     }
 
     pub(crate) fn until(&self, date_start: NaiveDate, date_end: NaiveDate) -> LogDays {
@@ -100,7 +184,82 @@ impl LogDays {
                 })
                 .cloned()
                 .collect(),
+            allocation: self.allocation,
+        }
+    }
+
+    /// Index of the first day logged on the given date
+    pub(crate) fn index_of(&self, date: NaiveDate) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.date().date == date)
+    }
+
+    /// Index of the day logged closest to the given date, preferring the
+    /// earlier day on a tie
+    pub(crate) fn nearest_index(&self, date: NaiveDate) -> Option<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| {
+                let days = (entry.date().date - date).num_days();
+                (days.abs(), days > 0)
+            })
+            .map(|(index, _)| index)
+    }
+
+    pub(crate) fn minutes_by_date(&self) -> HashMap<NaiveDate, i64> {
+        self.entries
+            .iter()
+            .fold(HashMap::new(), |mut minutes, day: &LogDay| {
+                *minutes.entry(day.date().date).or_insert(0) += day.duration_total().num_minutes();
+                minutes
+            })
+    }
+
+    /// Minutes logged per token in each window between consecutive
+    /// `boundaries`, computed in a single pass over the days
+    pub(crate) fn token_series(&self, kind: TokenKind, boundaries: &[NaiveDate]) -> TokenSeries {
+        let windows = boundaries.len().saturating_sub(1);
+        let mut series: HashMap<String, Vec<i64>> = HashMap::new();
+        if windows == 0 {
+            return TokenSeries { series };
         }
+        for day in self.entries.iter() {
+            let date = day.date().date;
+            if date < boundaries[0] || date >= boundaries[windows] {
+                continue;
+            }
+            let window = boundaries.partition_point(|boundary| *boundary <= date) - 1;
+            for tag_meta in day.summary(kind, self.allocation).iter() {
+                series
+                    .entry(tag_meta.tag.to_string())
+                    .or_insert_with(|| vec![0; windows])[window] += tag_meta.duration.num_minutes();
+            }
+        }
+        TokenSeries { series }
+    }
+
+    /// Minutes logged in each hour of the day by weekday (Monday first),
+    /// entries spanning several hours are split between them
+    pub(crate) fn minutes_by_weekday_hour(&self) -> [[u64; 24]; 7] {
+        let mut buckets = [[0; 24]; 7];
+        for day in self.entries.iter() {
+            let weekday = day.date().date.weekday().num_days_from_monday() as i64;
+            for log in day.iter() {
+                let start = log.time_range().start.num_seconds_from_midnight() as i64 / 60;
+                let end = start + log.time_range().duration().num_minutes();
+                let mut minute = start;
+                while minute < end {
+                    let next = (minute / 60 + 1) * 60;
+                    let bucket = &mut buckets[((weekday + minute / 1440) % 7) as usize]
+                        [((minute / 60) % 24) as usize];
+                    *bucket += (next.min(end) - minute) as u64;
+                    minute = next;
+                }
+            }
+        }
+        buckets
     }
 
     pub(crate) fn minutes_by_weekday(&self) -> Vec<(&str, u64)> {
@@ -137,16 +296,48 @@ impl LogDays {
 
 #[derive(Clone)]
 pub struct LogDay {
-    logs: Vec<LogEntry>,
+    /// Shared with the clones, which the filtered days mostly are
+    logs: Rc<Vec<LogEntry>>,
     date: LogDate,
+    off: Option<DayOff>,
+    /// Minutes logged, summed once
+    minutes: i64,
+    /// Summaries by token kind and allocation, made when first asked for
+    summaries: Rc<RefCell<Summaries>>,
 }
 
+type Summaries = HashMap<(TokenKind, Allocation), Rc<TagMetas>>;
+
 impl LogDay {
-    pub fn iter(&self) -> Iter<LogEntry> {
+    fn with_logs(date: LogDate, off: Option<DayOff>, logs: Vec<LogEntry>) -> LogDay {
+        LogDay {
+            minutes: logs
+                .iter()
+                .map(|log| log.time_range().duration().num_minutes())
+                .sum(),
+            logs: Rc::new(logs),
+            date,
+            off,
+            summaries: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, LogEntry> {
         self.logs().iter()
     }
 
-    pub fn from_entry<'a>(current_date: NaiveDateTime, entry: Entry) -> Self {
+    /// Whether the day has the same entries as `previous`. Days parsed from
+    /// the same text share the descriptions of their entries.
+    fn is_unchanged_from(&self, previous: &LogDay) -> bool {
+        self.off == previous.off
+            && self.logs.len() == previous.logs.len()
+            && self.iter().zip(previous.iter()).all(|(log, previous)| {
+                Rc::ptr_eq(&log.desription, &previous.desription)
+                    && log.time_range == previous.time_range
+            })
+    }
+
+    pub fn from_entry(current_date: NaiveDateTime, entry: Entry) -> Self {
         let mut logs: Vec<LogEntry> = vec![];
 
         // # resolve the end dates
@@ -157,14 +348,15 @@ impl LogDay {
         // 3. if today, then end date = now
         // 4. if end date not set and not today, then end date = start date
         for log in entry.logs.iter().rev() {
-            if log.time.end.is_some() {
+            if let Some(end) = log.time.end {
                 logs.push(LogEntry {
                     time_range: TimeRangeView {
                         start: log.time.start.time(),
-                        end: log.time.end.unwrap().time(),
+                        end: end.time(),
                         ongoing: false,
                     },
                     desription: log.description.clone(),
+                    source: log.source.clone(),
                 });
                 continue;
             }
@@ -176,6 +368,7 @@ impl LogDay {
                         ongoing: false,
                     },
                     desription: log.description.clone(),
+                    source: log.source.clone(),
                 });
                 continue;
             }
@@ -187,6 +380,7 @@ impl LogDay {
                         ongoing: true,
                     },
                     desription: log.description.clone(),
+                    source: log.source.clone(),
                 });
                 continue;
             }
@@ -198,31 +392,27 @@ impl LogDay {
                     ongoing: false,
                 },
                 desription: log.description.clone(),
+                source: log.source.clone(),
             });
         }
         logs.reverse();
 
-        LogDay {
-            logs,
-            date: LogDate {
+        LogDay::with_logs(
+            LogDate {
                 now: current_date,
                 date: entry.date_object(),
             },
-        }
+            entry.off,
+            logs,
+        )
     }
 
-    pub fn new<'a>(current_date: NaiveDateTime, entry: Entry) -> LogDay {
+    pub fn new(current_date: NaiveDateTime, entry: Entry) -> LogDay {
         LogDay::from_entry(current_date, entry)
     }
 
     pub fn duration_total(&self) -> LogDuration {
-        LogDuration {
-            duration: Duration::minutes(
-                self.logs()
-                    .iter()
-                    .fold(0, |c, l| c + l.time_range().duration().num_minutes()),
-            ),
-        }
+        LogDuration::from_minutes(self.minutes)
     }
 
     pub fn logs(&self) -> &Vec<LogEntry> {
@@ -233,51 +423,85 @@ impl LogDay {
         &self.date
     }
 
-    pub fn tag_summary(&self, kind: TokenKind) -> TagMetas {
-        let entry_map = self.iter().fold(
-            HashMap::new(),
-            |entry_map: HashMap<String, TagMeta>, log: &LogEntry| {
-                log.description().by_kind_refs(kind).iter().fold(
-                    entry_map,
-                    |mut acc: HashMap<String, TagMeta>, tag: &&Token| {
-                        let meta = acc.entry(tag.text().to_string()).or_insert(TagMeta {
-                            tag: tag.text().to_string(),
-                            kind: tag.kind,
-                            duration: LogDuration::from_minutes(0_i64),
-                            count: 0,
-                        });
-                        meta.count += 1;
-                        meta.duration.duration = meta
-                            .duration
-                            .duration
-                            .checked_add(&log.time_range().duration().duration)
-                            .expect("overflow occurred");
-                        acc
-                    },
-                )
-            },
-        );
+    /// Holiday or leave declared for the day in the timesheet
+    pub fn off(&self) -> Option<DayOff> {
+        self.off
+    }
+
+    pub fn tag_summary(&self, kind: TokenKind, allocation: Allocation) -> TagMetas {
+        self.summary(kind, allocation).as_ref().clone()
+    }
+
+    /// The summary of the tokens of the kind, made once for each allocation
+    fn summary(&self, kind: TokenKind, allocation: Allocation) -> Rc<TagMetas> {
+        self.summaries
+            .borrow_mut()
+            .entry((kind, allocation))
+            .or_insert_with(|| Rc::new(self.summarize(kind, allocation)))
+            .clone()
+    }
+
+    fn summarize(&self, kind: TokenKind, allocation: Allocation) -> TagMetas {
+        let mut entry_map: HashMap<String, TagMeta> = HashMap::new();
+        let mut logged = 0;
+        for log in self.iter() {
+            let allocated = log.allocate(kind, allocation);
+            if !allocated.is_empty() {
+                logged += log.time_range().duration().num_minutes();
+            }
+            for (token, minutes) in allocated {
+                let meta = entry_map
+                    .entry(token.text().to_string())
+                    .or_insert(TagMeta {
+                        tag: token.text().to_string(),
+                        kind: token.kind,
+                        duration: LogDuration::from_minutes(0_i64),
+                        count: 0,
+                    });
+                meta.count += 1;
+                meta.duration.duration = meta
+                    .duration
+                    .duration
+                    .checked_add(&Duration::minutes(minutes))
+                    .expect("overflow occurred");
+            }
+        }
 
         let mut tag_metas: Vec<TagMeta> = vec![];
         for (_, v) in entry_map {
             tag_metas.push(v)
         }
-        tag_metas.sort_by(|a, b| b.duration.duration.cmp(&a.duration.duration));
-        TagMetas { tag_metas }
+        tag_metas.sort_by_key(|tag_meta| Reverse(tag_meta.duration.duration));
+        TagMetas {
+            tag_metas,
+            logged: LogDuration::from_minutes(logged),
+        }
+    }
+
+    pub(crate) fn logs_with_token(&self, token: &Token) -> Vec<&LogEntry> {
+        self.logs
+            .iter()
+            .filter(|log| {
+                log.description()
+                    .by_kind_refs(token.kind)
+                    .iter()
+                    .any(|t| t.text == token.text)
+            })
+            .collect()
     }
 
     pub(crate) fn with_filter(&self, filter: &Filter) -> Self {
         if filter.criterias.is_empty() {
             return self.clone();
         }
-        Self {
-            date: self.date.clone(),
-            logs: self
-                .logs
+        LogDay::with_logs(
+            self.date.clone(),
+            self.off,
+            self.logs
                 .iter()
                 .filter(|log| {
                     (|tokens: &Tokens| {
-                        if tokens.len() == 0 {
+                        if tokens.is_empty() {
                             return true;
                         }
                         for criteria in filter.criterias.iter() {
@@ -295,52 +519,53 @@ impl LogDay {
                 })
                 .cloned()
                 .collect(),
-        }
+        )
     }
 
     pub(crate) fn description(&self) -> Tokens {
         let mut parts: Vec<Vec<Token>> = Vec::new();
 
-        let tags: Vec<Token> = self
-            .logs
-            .iter()
-            .flat_map(|l| {
-                l.description()
-                    .clone()
-                    .no_whitespace()
-                    .by_kind(TokenKind::Tag)
-                    .0
-            })
-            .unique()
-            .intersperse(Token::prose(" ".to_string()))
-            .collect();
+        let tags: Vec<Token> = Itertools::intersperse(
+            self.logs
+                .iter()
+                .flat_map(|l| {
+                    l.description()
+                        .clone()
+                        .no_whitespace()
+                        .by_kind(TokenKind::Tag)
+                        .0
+                })
+                .unique(),
+            Token::prose(" ".to_string()),
+        )
+        .collect();
 
-        let tickets: Vec<Token> = self
-            .logs
-            .iter()
-            .flat_map(|l| {
-                l.description()
-                    .clone()
-                    .no_whitespace()
-                    .by_kind(TokenKind::Ticket)
-                    .0
-            })
-            .unique()
-            .intersperse(Token::prose(" ".to_string()))
-            .collect();
+        let tickets: Vec<Token> = Itertools::intersperse(
+            self.logs
+                .iter()
+                .flat_map(|l| {
+                    l.description()
+                        .clone()
+                        .no_whitespace()
+                        .by_kind(TokenKind::Ticket)
+                        .0
+                })
+                .unique(),
+            Token::prose(" ".to_string()),
+        )
+        .collect();
 
-        let prose: Vec<Token> = self
-            .logs
-            .iter()
-            .flat_map(|l| {
+        let prose: Vec<Token> = Itertools::intersperse(
+            self.logs.iter().flat_map(|l| {
                 l.description()
                     .clone()
                     .no_whitespace()
                     .by_kind(TokenKind::Prose)
                     .0
-            })
-            .intersperse(Token::prose(" ".to_string()))
-            .collect();
+            }),
+            Token::prose(" ".to_string()),
+        )
+        .collect();
 
         if !tags.is_empty() {
             parts.push(tags);
@@ -353,27 +578,101 @@ impl LogDay {
         }
 
         Tokens(
-            parts
-                .into_iter()
-                .intersperse(vec![Token::prose(" ".to_string())])
+            Itertools::intersperse(parts.into_iter(), vec![Token::prose(" ".to_string())])
                 .flatten()
                 .collect(),
         )
     }
 }
 
+#[derive(Clone)]
 pub struct TagMetas {
     pub tag_metas: Vec<TagMeta>,
+    /// Duration of the entries with at least one of the tokens
+    pub logged: LogDuration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortKey {
+    Duration,
+    Count,
+    Name,
+    Share,
+}
+
+impl SortKey {
+    pub fn next(&self) -> SortKey {
+        match self {
+            SortKey::Duration => SortKey::Count,
+            SortKey::Count => SortKey::Name,
+            SortKey::Name => SortKey::Share,
+            SortKey::Share => SortKey::Duration,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    pub fn reverse(&self) -> SortOrder {
+        match self {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        }
+    }
 }
 
 impl TagMetas {
-    pub fn iter(&self) -> Iter<TagMeta> {
+    pub fn iter(&self) -> Iter<'_, TagMeta> {
         self.tag_metas.iter()
     }
     pub fn len(&self) -> usize {
         self.tag_metas.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.tag_metas.is_empty()
+    }
+
+    /// Sort by the given key, ties are always ordered by name
+    pub fn sort(&mut self, key: SortKey, order: SortOrder) {
+        self.tag_metas.sort_by(|a, b| {
+            let ordering = match key {
+                SortKey::Duration | SortKey::Share => {
+                    a.duration.num_minutes().cmp(&b.duration.num_minutes())
+                }
+                SortKey::Count => a.count.cmp(&b.count),
+                SortKey::Name => a.tag.to_lowercase().cmp(&b.tag.to_lowercase()),
+            };
+            match order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+            .then_with(|| a.tag.cmp(&b.tag))
+        });
+    }
+
+    /// Percentage of the total duration attributed to the given tag
+    pub fn share(&self, tag_meta: &TagMeta) -> f64 {
+        let total = self.duration().num_minutes();
+        if total == 0 {
+            return 0.0;
+        }
+        (tag_meta.duration.num_minutes() as f64 / total as f64) * 100.0
+    }
+
+    /// Duration attributed to more than one token when each gets the full
+    /// duration of an entry
+    pub fn overlap(&self) -> LogDuration {
+        LogDuration::from_minutes(
+            (self.duration().num_minutes() - self.logged.num_minutes()).max(0),
+        )
+    }
+
     pub fn duration(&self) -> LogDuration {
         let minutes = self.iter().fold(0, |mut carry, tag_meta| {
             carry += tag_meta.duration.num_minutes();
@@ -385,6 +684,27 @@ impl TagMetas {
     }
 }
 
+/// Minutes per token over a number of consecutive periods, oldest first
+pub struct TokenSeries {
+    series: HashMap<String, Vec<i64>>,
+}
+
+impl TokenSeries {
+    pub fn get(&self, tag: &str) -> &[i64] {
+        self.series.get(tag).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Change in minutes of the last period relative to the one before
+    pub fn delta(&self, tag: &str) -> i64 {
+        match self.get(tag) {
+            [.., previous, last] => last - previous,
+            [last] => *last,
+            [] => 0,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct TagMeta {
     pub tag: String,
     pub kind: TokenKind,
@@ -395,7 +715,8 @@ pub struct TagMeta {
 #[derive(Clone)]
 pub struct LogEntry {
     time_range: TimeRangeView,
-    desription: Tokens,
+    desription: Rc<Tokens>,
+    source: Option<Rc<Path>>,
 }
 
 impl LogEntry {
@@ -410,6 +731,67 @@ impl LogEntry {
     pub(crate) fn description(&self) -> &Tokens {
         &self.desription
     }
+
+    /// File the entry was read from
+    pub fn source(&self) -> Option<&Path> {
+        self.source.as_deref()
+    }
+
+    /// Minutes of the entry attributed to each of its distinct tokens of
+    /// the kind. Tokens without a weight share what the weighted tokens
+    /// leave of 100%.
+    pub(crate) fn allocate(&self, kind: TokenKind, allocation: Allocation) -> Vec<(&Token, i64)> {
+        let tokens: Vec<&Token> = self
+            .desription
+            .by_kind_refs(kind)
+            .into_iter()
+            .unique_by(|token| token.text())
+            .collect();
+        let minutes = self.time_range.duration().num_minutes();
+        let weights: Vec<f64> = match allocation {
+            Allocation::Full => return tokens.into_iter().map(|token| (token, minutes)).collect(),
+            Allocation::Even => vec![1.0; tokens.len()],
+            Allocation::Weighted => {
+                let weighted: u32 = tokens.iter().filter_map(|token| token.weight).sum();
+                let unweighted = tokens.iter().filter(|token| token.weight.is_none()).count();
+                let rest = (100.0 - weighted as f64).max(0.0) / unweighted.max(1) as f64;
+                tokens
+                    .iter()
+                    .map(|token| token.weight.map(f64::from).unwrap_or(rest))
+                    .collect()
+            }
+        };
+        tokens.into_iter().zip(split(minutes, &weights)).collect()
+    }
+}
+
+/// Split minutes by weight, the minutes lost to rounding down go to the
+/// largest remainders so that the parts add up to the whole. Without any
+/// weight the minutes are split evenly.
+pub(crate) fn split(minutes: i64, weights: &[f64]) -> Vec<i64> {
+    let total: f64 = weights.iter().sum();
+    let weights: Vec<f64> = match total > 0.0 {
+        true => weights.to_vec(),
+        false => vec![1.0; weights.len()],
+    };
+    let total: f64 = weights.iter().sum();
+    let exact: Vec<f64> = weights
+        .iter()
+        .map(|weight| minutes as f64 * weight / total)
+        .collect();
+    let mut parts: Vec<i64> = exact.iter().map(|part| part.floor() as i64).collect();
+    let mut left = minutes - parts.iter().sum::<i64>();
+    let by_remainder = (0..parts.len()).sorted_by(|a, b| {
+        (exact[*b] - exact[*b].floor()).total_cmp(&(exact[*a] - exact[*a].floor()))
+    });
+    for index in by_remainder {
+        if left <= 0 {
+            break;
+        }
+        parts[index] += 1;
+        left -= 1;
+    }
+    parts
 }
 
 #[derive(Clone)]
@@ -425,14 +807,24 @@ impl LogDate {
             && self.date.day() == self.now.day()
     }
 
+    pub(crate) fn naive_date(&self) -> NaiveDate {
+        self.date
+    }
+
     pub(crate) fn to_verbose_string(&self) -> String {
         self.date.format("%A %e %B, %Y").to_string()
     }
 
+    /// e.g. "Mon 05"
+    pub(crate) fn to_weekday_string(&self) -> String {
+        self.date.format("%a %d").to_string()
+    }
+
     pub(crate) fn to_compact_string(&self) -> String {
         self.date.format("%d/%m/%Y").to_string()
     }
 }
+#[derive(Clone)]
 pub struct LogDuration {
     duration: Duration,
 }
@@ -447,28 +839,39 @@ impl LogDuration {
         }
     }
 }
-impl ToString for LogDuration {
-    fn to_string(&self) -> String {
+impl Display for LogDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let hours = self.duration.num_minutes() / 60;
         let mins = self.duration.num_minutes() % 60;
         if 0 == hours {
-            return format!("{}m", mins);
+            return write!(f, "{}m", mins);
         }
 
-        format!("{}h{}m", hours, mins)
+        write!(f, "{}h{}m", hours, mins)
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct TimeRangeView {
     pub start: NaiveTime,
     pub end: NaiveTime,
     pub ongoing: bool,
 }
 
+impl Display for TimeRangeView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
 impl TimeRangeView {
-    pub fn to_string(&self) -> String {
-        format!("{}-{}", self.start, self.end)
+    /// Start and end on the date, an end before the start is on the next day
+    pub fn date_times(&self, date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+        let end = match self.end >= self.start {
+            true => date.and_time(self.end),
+            false => (date + Duration::days(1)).and_time(self.end),
+        };
+        (date.and_time(self.start), end)
     }
 
     pub fn duration(&self) -> LogDuration {
@@ -506,7 +909,8 @@ mod tests {
                 end: NaiveTime::from_hms(12, 0, 0),
                 ongoing: false,
             },
-            desription: Tokens::from_prose("foo".to_string()),
+            desription: Tokens::from_prose("foo".to_string()).into(),
+            source: None,
         };
         assert_eq!(50.0, l.percentage_of_day(1440));
     }
@@ -535,51 +939,101 @@ mod tests {
     fn test_calculates_duration() {
         {
             let entry = Entry {
-                date: Date::from_ymd(2022, 01, 01),
+                date: Date::from_ymd(2022, 1, 1),
+                off: None,
                 logs: vec![
                     Log {
                         time: TimeRange::from_start(Time::from_hm(10, 0)),
-                        description: Tokens::from_prose("foo".to_string()),
+                        description: Tokens::from_prose("foo".to_string()).into(),
+                        source: None,
                     },
                     Log {
                         time: TimeRange::from_start(Time::from_hm(11, 0)),
-                        description: Tokens::from_prose("foo".to_string()),
+                        description: Tokens::from_prose("foo".to_string()).into(),
+                        source: None,
                     },
                     Log {
                         time: TimeRange::from_start(Time::from_hm(13, 0)),
-                        description: Tokens::from_prose("foo".to_string()),
+                        description: Tokens::from_prose("foo".to_string()).into(),
+                        source: None,
                     },
                 ],
             };
-            let time = NaiveDate::from_ymd(2022, 01, 01).and_hms(0, 0, 0);
+            let time = NaiveDate::from_ymd(2022, 1, 1).and_hms(0, 0, 0);
             let view = LogDay::new(time, entry);
             assert_eq!("10:00:00-11:00:00", view.logs[0].time_range().to_string())
         }
     }
 
+    #[test]
+    fn keeps_the_summaries_of_unchanged_days() {
+        let day = |day, description: &Rc<Tokens>| Entry {
+            date: Date::from_ymd(2022, 1, day),
+            off: None,
+            logs: vec![Log {
+                time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(11, 0)),
+                description: description.clone(),
+                source: None,
+            }],
+        };
+        let review: Rc<Tokens> = Tokens::new(vec![Token::tag("review".to_string())]).into();
+        let time = NaiveDate::from_ymd(2022, 1, 3).and_hms(0, 0, 0);
+        let previous = LogDays::from_entries(
+            time,
+            vec![day(1, &review), day(2, &review), day(3, &review)],
+        );
+        previous.tag_summary(TokenKind::Tag);
+
+        // the second day parsed again, the third changed
+        let parsed_again: Rc<Tokens> = Tokens::new(vec![Token::tag("review".to_string())]).into();
+        let mut changed = day(3, &review);
+        changed.logs[0].time =
+            TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(12, 0));
+        let days =
+            LogDays::from_entries(time, vec![day(1, &review), day(2, &parsed_again), changed])
+                .with_summaries_of(&previous);
+        let kept: Vec<bool> = days
+            .iter()
+            .zip(previous.iter())
+            .map(|(day, previous)| Rc::ptr_eq(&day.summaries, &previous.summaries))
+            .collect();
+        assert_eq!(vec![true, false, false], kept);
+        assert_eq!(
+            240,
+            days.tag_summary(TokenKind::Tag).tag_metas[0]
+                .duration
+                .num_minutes()
+        );
+    }
+
     #[test]
     fn test_view_tag_summary() {
         let entry = Entry {
-            date: Date::from_ymd(2022, 01, 01),
+            date: Date::from_ymd(2022, 1, 1),
+            off: None,
             logs: vec![
                 Log {
                     time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(10, 30)),
-                    description: Tokens::new(vec![Token::tag("foobar".to_string())]),
+                    description: Tokens::new(vec![Token::tag("foobar".to_string())]).into(),
+                    source: None,
                 },
                 Log {
                     time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(11, 0)),
-                    description: Tokens::new(vec![
+                    description: Rc::new(Tokens::new(vec![
                         Token::tag("barfoo".to_string()),
                         Token::tag("foobar".to_string()),
-                    ]),
+                    ])),
+                    source: None,
                 },
             ],
         };
-        let time = NaiveDate::from_ymd(2022, 01, 01).and_hms(0, 0, 0);
+        let time = NaiveDate::from_ymd(2022, 1, 1).and_hms(0, 0, 0);
         let view = LogDay::new(time, entry);
-        let summary = view.tag_summary(TokenKind::Tag);
+        let summary = view.tag_summary(TokenKind::Tag, Allocation::Full);
 
         assert_eq!(2, summary.len());
+        assert_eq!(90, summary.logged.num_minutes());
+        assert_eq!(60, summary.overlap().num_minutes());
 
         assert_eq!("barfoo".to_string(), summary.tag_metas[1].tag);
         assert_eq!(1, summary.tag_metas[1].count);
@@ -588,6 +1042,121 @@ mod tests {
         assert_eq!("foobar".to_string(), summary.tag_metas[0].tag);
         assert_eq!(2, summary.tag_metas[0].count);
         assert_eq!(90, summary.tag_metas[0].duration.num_minutes());
+
+        let summary = view.tag_summary(TokenKind::Tag, Allocation::Even);
+        assert_eq!(60, summary.tag_metas[0].duration.num_minutes());
+        assert_eq!(30, summary.tag_metas[1].duration.num_minutes());
+        assert_eq!(0, summary.overlap().num_minutes());
+    }
+
+    #[test]
+    fn test_allocate_by_weight() {
+        let entry = |tokens: Vec<Token>| LogEntry {
+            time_range: TimeRangeView {
+                start: NaiveTime::from_hms(10, 0, 0),
+                end: NaiveTime::from_hms(11, 40, 0),
+                ongoing: false,
+            },
+            desription: Tokens::new(tokens).into(),
+            source: None,
+        };
+        let weighted = |text: &str, weight| Token {
+            weight,
+            ..Token::ticket(text.to_string())
+        };
+        let minutes = |entry: &LogEntry, allocation| -> Vec<(String, i64)> {
+            entry
+                .allocate(TokenKind::Ticket, allocation)
+                .iter()
+                .map(|(token, minutes)| (token.text().to_string(), *minutes))
+                .collect()
+        };
+        let pairs = |pairs: &[(&str, i64)]| -> Vec<(String, i64)> {
+            pairs
+                .iter()
+                .map(|(text, minutes)| (text.to_string(), *minutes))
+                .collect()
+        };
+
+        let entry_1 = entry(vec![
+            weighted("EX-1", Some(70)),
+            weighted("EX-2", None),
+            Token::tag("foo".to_string()),
+        ]);
+        assert_eq!(
+            pairs(&[("EX-1", 100), ("EX-2", 100)]),
+            minutes(&entry_1, Allocation::Full)
+        );
+        assert_eq!(
+            pairs(&[("EX-1", 50), ("EX-2", 50)]),
+            minutes(&entry_1, Allocation::Even)
+        );
+        assert_eq!(
+            pairs(&[("EX-1", 70), ("EX-2", 30)]),
+            minutes(&entry_1, Allocation::Weighted)
+        );
+
+        // the remainder of 100 minutes in three goes to the first
+        let entry_2 = entry(vec![
+            weighted("EX-1", None),
+            weighted("EX-2", None),
+            weighted("EX-3", None),
+            weighted("EX-1", None),
+        ]);
+        assert_eq!(
+            pairs(&[("EX-1", 34), ("EX-2", 33), ("EX-3", 33)]),
+            minutes(&entry_2, Allocation::Weighted)
+        );
+
+        // weights over 100% are normalized, zero weights fall back to even
+        let entry_3 = entry(vec![
+            weighted("EX-1", Some(150)),
+            weighted("EX-2", Some(50)),
+        ]);
+        assert_eq!(
+            pairs(&[("EX-1", 75), ("EX-2", 25)]),
+            minutes(&entry_3, Allocation::Weighted)
+        );
+        let entry_4 = entry(vec![weighted("EX-1", Some(0)), weighted("EX-2", Some(0))]);
+        assert_eq!(
+            pairs(&[("EX-1", 50), ("EX-2", 50)]),
+            minutes(&entry_4, Allocation::Weighted)
+        );
+    }
+
+    #[test]
+    fn test_tag_metas_sort() {
+        let tag_meta = |tag: &str, minutes: i64, count: usize| TagMeta {
+            tag: tag.to_string(),
+            kind: TokenKind::Tag,
+            duration: LogDuration::from_minutes(minutes),
+            count,
+        };
+        let mut metas = TagMetas {
+            tag_metas: vec![
+                tag_meta("bravo", 30, 3),
+                tag_meta("Alpha", 60, 1),
+                tag_meta("charlie", 30, 2),
+            ],
+            logged: LogDuration::from_minutes(120),
+        };
+        let tags = |metas: &TagMetas| -> Vec<String> {
+            metas.iter().map(|meta| meta.tag.to_string()).collect()
+        };
+
+        metas.sort(SortKey::Duration, SortOrder::Descending);
+        assert_eq!(vec!["Alpha", "bravo", "charlie"], tags(&metas));
+
+        metas.sort(SortKey::Duration, SortOrder::Ascending);
+        assert_eq!(vec!["bravo", "charlie", "Alpha"], tags(&metas));
+
+        metas.sort(SortKey::Count, SortOrder::Descending);
+        assert_eq!(vec!["bravo", "charlie", "Alpha"], tags(&metas));
+
+        metas.sort(SortKey::Name, SortOrder::Ascending);
+        assert_eq!(vec!["Alpha", "bravo", "charlie"], tags(&metas));
+
+        assert_eq!(50.0, metas.share(&metas.tag_metas[0]));
     }
 
     #[test]
@@ -595,10 +1164,12 @@ mod tests {
         let mut entries = vec![];
         for day in 1..30 {
             entries.push(Entry {
-                date: Date::from_ymd(2022, 01, day),
+                date: Date::from_ymd(2022, 1, day),
+                off: None,
                 logs: vec![Log {
                     time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(12, 30)),
-                    description: Tokens::new(vec![Token::tag("foobar".to_string())]),
+                    description: Tokens::new(vec![Token::tag("foobar".to_string())]).into(),
+                    source: None,
                 }],
             });
         }
@@ -610,26 +1181,92 @@ mod tests {
         assert_eq!(&("Mon", 600), minutes_by_weekday.first().unwrap());
     }
 
+    #[test]
+    fn test_minutes_by_weekday_hour() {
+        let log = |start: (u32, u32), end: (u32, u32)| Log {
+            time: TimeRange::from_start_end(
+                Time::from_hm(start.0, start.1),
+                Time::from_hm(end.0, end.1),
+            ),
+            description: Tokens::new(vec![]).into(),
+            source: None,
+        };
+        // a saturday
+        let days = LogDays::new(vec![Entry {
+            date: Date::from_ymd(2022, 1, 1),
+            off: None,
+            logs: vec![log((10, 30), (12, 15)), log((23, 30), (0, 45))],
+        }]);
+
+        let buckets = days.minutes_by_weekday_hour();
+        assert_eq!(30, buckets[5][10]);
+        assert_eq!(60, buckets[5][11]);
+        assert_eq!(15, buckets[5][12]);
+        assert_eq!(30, buckets[5][23]);
+        // rolls over into sunday
+        assert_eq!(45, buckets[6][0]);
+        assert_eq!(180, buckets.iter().flatten().sum::<u64>());
+    }
+
+    #[test]
+    fn test_token_series() {
+        let entry = |day, tag: &str| Entry {
+            date: Date::from_ymd(2022, 1, day),
+            off: None,
+            logs: vec![Log {
+                time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(11, 0)),
+                description: Tokens::new(vec![Token::tag(tag.to_string())]).into(),
+                source: None,
+            }],
+        };
+        let days = LogDays::new(vec![
+            entry(1, "foo"),
+            entry(3, "foo"),
+            entry(8, "foo"),
+            entry(8, "bar"),
+            entry(20, "foo"),
+        ]);
+
+        let series = days.token_series(
+            TokenKind::Tag,
+            &[
+                NaiveDate::from_ymd(2022, 1, 1),
+                NaiveDate::from_ymd(2022, 1, 8),
+                NaiveDate::from_ymd(2022, 1, 15),
+            ],
+        );
+        assert_eq!(&[120, 60], series.get("foo"));
+        assert_eq!(&[0, 60], series.get("bar"));
+        assert!(series.get("baz").is_empty());
+        assert_eq!(-60, series.delta("foo"));
+        assert_eq!(60, series.delta("bar"));
+    }
+
     #[test]
     fn test_filters_by_tag() {
         let days = LogDays::new(vec![Entry {
-            date: Date::from_ymd(2022, 01, 1),
+            date: Date::from_ymd(2022, 1, 1),
+            off: None,
             logs: vec![
                 Log {
                     time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(12, 30)),
-                    description: Tokens::new(vec![Token::tag("foobar".to_string())]),
+                    description: Tokens::new(vec![Token::tag("foobar".to_string())]).into(),
+                    source: None,
                 },
                 Log {
                     time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(12, 30)),
-                    description: Tokens::new(vec![Token::tag("barfoo".to_string())]),
+                    description: Tokens::new(vec![Token::tag("barfoo".to_string())]).into(),
+                    source: None,
                 },
                 Log {
                     time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(12, 30)),
-                    description: Tokens::new(vec![Token::ticket("FOO-1234".to_string())]),
+                    description: Tokens::new(vec![Token::ticket("FOO-1234".to_string())]).into(),
+                    source: None,
                 },
                 Log {
                     time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(12, 30)),
-                    description: Tokens::new(vec![Token::tag("foobar".to_string())]),
+                    description: Tokens::new(vec![Token::tag("foobar".to_string())]).into(),
+                    source: None,
                 },
             ],
         }]);
@@ -648,16 +1285,84 @@ mod tests {
         assert_eq!(1, filtered.entries[0].logs.len());
     }
 
+    #[test]
+    fn test_logs_with_token() {
+        let days = LogDays::new(vec![
+            Entry {
+                date: Date::from_ymd(2022, 1, 1),
+                off: None,
+                logs: vec![
+                    Log {
+                        time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(11, 0)),
+                        description: Tokens::new(vec![Token::ticket("FOO-1".to_string())]).into(),
+                        source: None,
+                    },
+                    Log {
+                        time: TimeRange::from_start_end(Time::from_hm(11, 0), Time::from_hm(12, 0)),
+                        description: Tokens::new(vec![Token::tag("FOO-1".to_string())]).into(),
+                        source: None,
+                    },
+                ],
+            },
+            Entry {
+                date: Date::from_ymd(2022, 1, 2),
+                off: None,
+                logs: vec![Log {
+                    time: TimeRange::from_start_end(Time::from_hm(9, 0), Time::from_hm(9, 30)),
+                    description: Rc::new(Tokens::new(vec![
+                        Token::tag("foobar".to_string()),
+                        Token::ticket("FOO-1".to_string()),
+                    ])),
+                    source: None,
+                }],
+            },
+        ]);
+
+        let logs = days.logs_with_token(&Token::ticket("FOO-1".to_string()));
+        assert_eq!(2, logs.len());
+        assert_eq!("01/01/2022", logs[0].0.to_compact_string());
+        assert_eq!("02/01/2022", logs[1].0.to_compact_string());
+        assert_eq!(30, logs[1].1.time_range().duration().num_minutes());
+    }
+
+    #[test]
+    fn test_minutes_by_date() {
+        let entry = |day| Entry {
+            date: Date::from_ymd(2022, 1, day),
+            off: None,
+            logs: vec![Log {
+                time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(11, 0)),
+                description: Tokens::new(vec![]).into(),
+                source: None,
+            }],
+        };
+        let days = LogDays::new(vec![entry(1), entry(3), entry(3)]);
+
+        let minutes = days.minutes_by_date();
+        assert_eq!(Some(&60), minutes.get(&NaiveDate::from_ymd(2022, 1, 1)));
+        assert_eq!(Some(&120), minutes.get(&NaiveDate::from_ymd(2022, 1, 3)));
+        assert_eq!(None, minutes.get(&NaiveDate::from_ymd(2022, 1, 2)));
+
+        assert_eq!(Some(1), days.index_of(NaiveDate::from_ymd(2022, 1, 3)));
+        assert_eq!(None, days.index_of(NaiveDate::from_ymd(2022, 1, 2)));
+
+        assert_eq!(Some(0), days.nearest_index(NaiveDate::from_ymd(2022, 1, 2)));
+        assert_eq!(Some(1), days.nearest_index(NaiveDate::from_ymd(2022, 1, 3)));
+        assert_eq!(Some(1), days.nearest_index(NaiveDate::from_ymd(2023, 1, 1)));
+    }
+
     #[test]
     fn test_filters_not() {
         let days = LogDays::new(vec![Entry {
-            date: Date::from_ymd(2022, 01, 1),
+            date: Date::from_ymd(2022, 1, 1),
+            off: None,
             logs: vec![Log {
                 time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(12, 30)),
-                description: Tokens::new(vec![
+                description: Rc::new(Tokens::new(vec![
                     Token::prose("baz".to_string()),
                     Token::tag("foobar".to_string()),
-                ]),
+                ])),
+                source: None,
             }],
         }]);
         assert_eq!(1, days.entries[0].logs.len());
@@ -677,10 +1382,12 @@ mod tests {
         let day = LogDay::from_entry(
             Local::now().naive_local(),
             Entry {
-                date: Date::from_ymd(2022, 01, 1),
+                date: Date::from_ymd(2022, 1, 1),
+                off: None,
                 logs: vec![Log {
                     time: TimeRange::from_start_end(Time::from_hm(10, 0), Time::from_hm(12, 30)),
-                    description: Tokens::new(vec![Token::tag("foobar".to_string())]),
+                    description: Tokens::new(vec![Token::tag("foobar".to_string())]).into(),
+                    source: None,
                 }],
             },
         );
@@ -0,0 +1,448 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    app::config::{Allocation, Rounding, RoundingMode, RoundingScope},
+    parser::token::TokenKind,
+};
+
+use super::model::{split, LogDay, LogDays, LogDuration, LogEntry};
+
+/// Round minutes to a multiple of `granularity`, when rounding to the
+/// nearest multiple halves are rounded up
+pub fn round(minutes: i64, mode: RoundingMode, granularity: u32) -> i64 {
+    let granularity = granularity as i64;
+    if granularity <= 1 {
+        return minutes;
+    }
+    let multiples = match mode {
+        RoundingMode::Up => (minutes + granularity - 1).div_euclid(granularity),
+        RoundingMode::Nearest => (minutes + granularity / 2).div_euclid(granularity),
+        RoundingMode::Down => minutes.div_euclid(granularity),
+    };
+    multiples * granularity
+}
+
+/// Rounded total of the days for the scope of the rounding. Entries with
+/// several tickets count once towards the total, so that it is the sum of
+/// the rounded tickets.
+pub fn total(rounding: &Rounding, log_days: &LogDays) -> i64 {
+    let round = |minutes| round(minutes, rounding.mode, rounding.minutes);
+    let allocation = counted_once(log_days.allocation());
+    match rounding.scope {
+        RoundingScope::Entry => entries(log_days).map(|entry| round(minutes(entry))).sum(),
+        RoundingScope::Day => log_days
+            .iter()
+            .map(|day| round(day.duration_total().num_minutes()))
+            .sum(),
+        RoundingScope::Ticket => by_ticket(log_days, allocation)
+            .into_values()
+            .map(round)
+            .sum(),
+        RoundingScope::Interval => round(log_days.duration_total().num_minutes()),
+    }
+}
+
+/// Rounded total of a day, none if the scope is wider than a day
+pub fn day_total(rounding: &Rounding, day: &LogDay) -> Option<i64> {
+    let round = |minutes| round(minutes, rounding.mode, rounding.minutes);
+    match rounding.scope {
+        RoundingScope::Entry => Some(day.iter().map(|entry| round(minutes(entry))).sum()),
+        RoundingScope::Day => Some(round(day.duration_total().num_minutes())),
+        RoundingScope::Ticket | RoundingScope::Interval => None,
+    }
+}
+
+/// Rounded minutes of each token of the kind, for the scopes that can be
+/// attributed to tokens. In the entry scope each entry is rounded as a
+/// whole and then split between its tokens as allocated.
+pub fn token_totals(rounding: &Rounding, log_days: &LogDays, kind: TokenKind) -> RoundedTokens {
+    let round = |minutes| round(minutes, rounding.mode, rounding.minutes);
+    let mut totals: HashMap<String, i64> = HashMap::new();
+    match (rounding.scope, kind) {
+        (RoundingScope::Entry, _) => {
+            for entry in entries(log_days) {
+                let rounded = round(minutes(entry));
+                let shares = entry.allocate(kind, log_days.allocation());
+                let split = match log_days.allocation() {
+                    Allocation::Full => vec![rounded; shares.len()],
+                    _ => {
+                        let weights: Vec<f64> =
+                            shares.iter().map(|(_, minutes)| *minutes as f64).collect();
+                        split(rounded, &weights)
+                    }
+                };
+                for ((token, _), minutes) in shares.into_iter().zip(split) {
+                    *totals.entry(token.text().to_string()).or_insert(0) += minutes;
+                }
+            }
+        }
+        (RoundingScope::Ticket, TokenKind::Ticket) => {
+            for (ticket, minutes) in by_ticket(log_days, log_days.allocation()) {
+                if let Some(ticket) = ticket {
+                    totals.insert(ticket, round(minutes));
+                }
+            }
+        }
+        _ => (),
+    }
+    RoundedTokens {
+        minutes: totals,
+        side_by_side: rounding.side_by_side,
+    }
+}
+
+/// Minutes of the days by item, e.g. by ticket or by date, rounded if
+/// configured. The rounded minutes of each part the scope rounds are split
+/// between its items by their minutes, so that the items add up to `total`.
+pub fn by_item<K: Ord>(
+    rounding: Option<&Rounding>,
+    log_days: &LogDays,
+    item: impl Fn(&LogDay, Option<&str>) -> K,
+) -> BTreeMap<K, i64> {
+    let allocation = counted_once(log_days.allocation());
+    let mut parts: HashMap<Part, Vec<(K, i64)>> = HashMap::new();
+    let mut entries = 0;
+    for (index, day) in log_days.iter().enumerate() {
+        for entry in day.iter() {
+            entries += 1;
+            for (ticket, minutes) in ticket_shares(entry, allocation) {
+                let part = match rounding.map(|rounding| rounding.scope) {
+                    Some(RoundingScope::Entry) => (entries, None),
+                    Some(RoundingScope::Day) => (index, None),
+                    Some(RoundingScope::Ticket) => (0, ticket.clone()),
+                    Some(RoundingScope::Interval) | None => (0, None),
+                };
+                parts
+                    .entry(part)
+                    .or_default()
+                    .push((item(day, ticket.as_deref()), minutes));
+            }
+        }
+    }
+    let mut items = BTreeMap::new();
+    for part in parts.into_values() {
+        let minutes = part.iter().map(|(_, minutes)| minutes).sum();
+        let rounded = match rounding {
+            Some(rounding) => round(minutes, rounding.mode, rounding.minutes),
+            None => minutes,
+        };
+        let weights: Vec<f64> = part.iter().map(|(_, minutes)| *minutes as f64).collect();
+        for ((item, _), minutes) in part.into_iter().zip(split(rounded, &weights)) {
+            *items.entry(item).or_insert(0) += minutes;
+        }
+    }
+    items
+}
+
+/// Part of the days rounded as a whole: an entry, a day,
+/// a ticket or the whole interval
+type Part = (usize, Option<String>);
+
+/// Rounded minutes by token name
+pub struct RoundedTokens {
+    minutes: HashMap<String, i64>,
+    pub side_by_side: bool,
+}
+
+impl RoundedTokens {
+    pub fn get(&self, token: &str) -> Option<i64> {
+        self.minutes.get(token).copied()
+    }
+}
+
+/// The raw duration or, if rounded, the rounded duration or both
+pub fn format(raw: i64, rounded: Option<i64>, side_by_side: bool) -> String {
+    match rounded {
+        Some(rounded) if side_by_side => format!(
+            "{} ({})",
+            LogDuration::from_minutes(raw),
+            LogDuration::from_minutes(rounded)
+        ),
+        Some(rounded) => LogDuration::from_minutes(rounded).to_string(),
+        None => LogDuration::from_minutes(raw).to_string(),
+    }
+}
+
+fn entries(log_days: &LogDays) -> impl Iterator<Item = &LogEntry> {
+    log_days.iter().flat_map(|day| day.iter())
+}
+
+fn minutes(entry: &LogEntry) -> i64 {
+    entry.time_range().duration().num_minutes()
+}
+
+/// Minutes of the entry attributed to each of its tickets, or to none if it
+/// has no ticket
+fn ticket_shares(entry: &LogEntry, allocation: Allocation) -> Vec<(Option<String>, i64)> {
+    let shares = entry.allocate(TokenKind::Ticket, allocation);
+    if shares.is_empty() {
+        return vec![(None, minutes(entry))];
+    }
+    shares
+        .into_iter()
+        .map(|(ticket, minutes)| (Some(ticket.text().to_string()), minutes))
+        .collect()
+}
+
+/// Allocation counting each entry once. In full, an entry counts towards
+/// each of its tickets, so a total splits it evenly instead.
+fn counted_once(allocation: Allocation) -> Allocation {
+    match allocation {
+        Allocation::Full => Allocation::Even,
+        allocation => allocation,
+    }
+}
+
+/// Minutes by ticket as allocated, entries without a ticket are grouped
+/// together
+fn by_ticket(log_days: &LogDays, allocation: Allocation) -> HashMap<Option<String>, i64> {
+    let mut totals = HashMap::new();
+    for (ticket, minutes) in entries(log_days).flat_map(|entry| ticket_shares(entry, allocation)) {
+        *totals.entry(ticket).or_insert(0) += minutes;
+    }
+    totals
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{
+        timesheet::{Date, Entry, Log, Time, TimeRange, Tokens},
+        token::Token,
+    };
+
+    use super::*;
+
+    fn rounding(mode: RoundingMode, scope: RoundingScope) -> Rounding {
+        Rounding {
+            mode,
+            minutes: 15,
+            scope,
+            side_by_side: false,
+        }
+    }
+
+    fn log(start: (u32, u32), end: (u32, u32), ticket: &str) -> Log {
+        Log {
+            time: TimeRange::from_start_end(
+                Time::from_hm(start.0, start.1),
+                Time::from_hm(end.0, end.1),
+            ),
+            description: Tokens::new(vec![Token::ticket(ticket.to_string())]).into(),
+            source: None,
+        }
+    }
+
+    fn log_days() -> LogDays {
+        let day = |day, logs| Entry {
+            date: Date::from_ymd(2026, 1, day),
+            off: None,
+            logs,
+        };
+        LogDays::new(vec![
+            // 10m and 20m on EX-1, 7m on EX-2
+            day(
+                5,
+                vec![log((9, 0), (9, 10), "EX-1"), log((9, 10), (9, 30), "EX-1")],
+            ),
+            day(6, vec![log((9, 0), (9, 7), "EX-2")]),
+        ])
+    }
+
+    #[test]
+    fn rounds_up_at_boundaries() {
+        assert_eq!(0, round(0, RoundingMode::Up, 15));
+        assert_eq!(15, round(1, RoundingMode::Up, 15));
+        assert_eq!(15, round(15, RoundingMode::Up, 15));
+        assert_eq!(30, round(16, RoundingMode::Up, 15));
+        assert_eq!(6, round(6, RoundingMode::Up, 6));
+        assert_eq!(12, round(7, RoundingMode::Up, 6));
+    }
+
+    #[test]
+    fn rounds_down_at_boundaries() {
+        assert_eq!(0, round(14, RoundingMode::Down, 15));
+        assert_eq!(15, round(15, RoundingMode::Down, 15));
+        assert_eq!(15, round(29, RoundingMode::Down, 15));
+        assert_eq!(6, round(11, RoundingMode::Down, 6));
+    }
+
+    #[test]
+    fn rounds_halves_up_to_nearest() {
+        assert_eq!(0, round(7, RoundingMode::Nearest, 15));
+        assert_eq!(15, round(8, RoundingMode::Nearest, 15));
+        assert_eq!(15, round(22, RoundingMode::Nearest, 15));
+        assert_eq!(30, round(23, RoundingMode::Nearest, 15));
+        assert_eq!(0, round(2, RoundingMode::Nearest, 6));
+        assert_eq!(6, round(3, RoundingMode::Nearest, 6));
+    }
+
+    #[test]
+    fn does_not_round_without_granularity() {
+        assert_eq!(7, round(7, RoundingMode::Up, 0));
+        assert_eq!(7, round(7, RoundingMode::Nearest, 1));
+    }
+
+    #[test]
+    fn rounds_totals_by_scope() {
+        let log_days = log_days();
+        let total = |scope| total(&rounding(RoundingMode::Up, scope), &log_days);
+        // 15 + 30 + 15
+        assert_eq!(60, total(RoundingScope::Entry));
+        // 30 + 15
+        assert_eq!(45, total(RoundingScope::Day));
+        // 30 + 15
+        assert_eq!(45, total(RoundingScope::Ticket));
+        // 37
+        assert_eq!(45, total(RoundingScope::Interval));
+        assert_eq!(
+            30,
+            super::total(
+                &rounding(RoundingMode::Nearest, RoundingScope::Interval),
+                &log_days
+            )
+        );
+    }
+
+    #[test]
+    fn rounds_days_and_tokens_by_scope() {
+        let log_days = log_days();
+        let day = log_days.iter().next().unwrap();
+        assert_eq!(
+            Some(45),
+            day_total(&rounding(RoundingMode::Up, RoundingScope::Entry), day)
+        );
+        assert_eq!(
+            Some(30),
+            day_total(&rounding(RoundingMode::Up, RoundingScope::Day), day)
+        );
+        assert_eq!(
+            None,
+            day_total(&rounding(RoundingMode::Up, RoundingScope::Ticket), day)
+        );
+
+        let tokens =
+            |scope, kind| token_totals(&rounding(RoundingMode::Up, scope), &log_days, kind);
+        assert_eq!(
+            Some(45),
+            tokens(RoundingScope::Entry, TokenKind::Ticket).get("EX-1")
+        );
+        assert_eq!(
+            Some(30),
+            tokens(RoundingScope::Ticket, TokenKind::Ticket).get("EX-1")
+        );
+        assert_eq!(
+            Some(15),
+            tokens(RoundingScope::Ticket, TokenKind::Ticket).get("EX-2")
+        );
+        assert_eq!(
+            None,
+            tokens(RoundingScope::Ticket, TokenKind::Tag).get("EX-1")
+        );
+        assert_eq!(
+            None,
+            tokens(RoundingScope::Day, TokenKind::Ticket).get("EX-1")
+        );
+    }
+
+    #[test]
+    fn rounded_tickets_add_up_to_the_total() {
+        let ticket = |text: &str, weight| Token {
+            weight,
+            whitespace: " ".to_string(),
+            ..Token::ticket(text.to_string())
+        };
+        let log = |start, end, tickets: Vec<Token>| Log {
+            time: TimeRange::from_start_end(Time::from_hm(9, start), Time::from_hm(9, end)),
+            description: Tokens::new(tickets).into(),
+            source: None,
+        };
+        // 20m on EX-1 and EX-2, EX-1 taking 75% when weighted, and 10m on EX-1
+        let entries = vec![Entry {
+            date: Date::from_ymd(2026, 1, 5),
+            off: None,
+            logs: vec![
+                log(0, 20, vec![ticket("EX-1", Some(75)), ticket("EX-2", None)]),
+                log(20, 30, vec![ticket("EX-1", None)]),
+            ],
+        }];
+        let rounded = |allocation, scope| {
+            let log_days = LogDays::new(entries.clone()).with_allocation(allocation);
+            let rounding = rounding(RoundingMode::Up, scope);
+            let tickets = token_totals(&rounding, &log_days, TokenKind::Ticket);
+            let day = day_total(&rounding, log_days.iter().next().unwrap());
+            (
+                tickets.get("EX-1").unwrap(),
+                tickets.get("EX-2").unwrap(),
+                total(&rounding, &log_days),
+                day,
+            )
+        };
+        // 30m split 15m + 15m, 15m
+        assert_eq!(
+            (30, 15, 45, Some(45)),
+            rounded(Allocation::Even, RoundingScope::Entry)
+        );
+        // 20m, 10m
+        assert_eq!(
+            (30, 15, 45, None),
+            rounded(Allocation::Even, RoundingScope::Ticket)
+        );
+        // 30m split 23m + 7m, 15m
+        assert_eq!(
+            (38, 7, 45, Some(45)),
+            rounded(Allocation::Weighted, RoundingScope::Entry)
+        );
+        // 25m, 5m
+        assert_eq!(
+            (30, 15, 45, None),
+            rounded(Allocation::Weighted, RoundingScope::Ticket)
+        );
+    }
+
+    #[test]
+    fn rounds_entries_before_splitting_them() {
+        // 10m on EX-1 and EX-2
+        let entries = vec![Entry {
+            date: Date::from_ymd(2026, 1, 5),
+            off: None,
+            logs: vec![Log {
+                time: TimeRange::from_start_end(Time::from_hm(9, 0), Time::from_hm(9, 10)),
+                description: Tokens::new(vec![
+                    Token::ticket("EX-1".to_string()),
+                    Token {
+                        whitespace: " ".to_string(),
+                        ..Token::ticket("EX-2".to_string())
+                    },
+                ])
+                .into(),
+                source: None,
+            }],
+        }];
+        let rounding = rounding(RoundingMode::Up, RoundingScope::Entry);
+        for allocation in [Allocation::Full, Allocation::Even, Allocation::Weighted] {
+            let log_days = LogDays::new(entries.clone()).with_allocation(allocation);
+            assert_eq!(15, total(&rounding, &log_days));
+            assert_eq!(
+                Some(15),
+                day_total(&rounding, log_days.iter().next().unwrap())
+            );
+            let items = by_item(Some(&rounding), &log_days, |_, ticket| {
+                ticket.map(str::to_string)
+            });
+            assert_eq!(15, items.values().sum::<i64>());
+        }
+        let log_days = LogDays::new(entries).with_allocation(Allocation::Even);
+        let tickets = token_totals(&rounding, &log_days, TokenKind::Ticket);
+        assert_eq!(
+            (Some(8), Some(7)),
+            (tickets.get("EX-1"), tickets.get("EX-2"))
+        );
+    }
+
+    #[test]
+    fn formats_raw_and_rounded() {
+        assert_eq!("37m", format(37, None, true));
+        assert_eq!("45m", format(37, Some(45), false));
+        assert_eq!("37m (45m)", format(37, Some(45), true));
+    }
+}
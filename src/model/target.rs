@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::{app::config::Config, parser::timesheet::DayOff};
+
+use super::model::LogDays;
+
+/// Time logged against the time expected over a number of days
+#[derive(Debug, Default, PartialEq)]
+pub struct Progress {
+    pub logged: i64,
+    pub target: i64,
+}
+
+impl Progress {
+    /// Minutes worked over (positive) or under (negative) the target
+    pub fn over_under(&self) -> i64 {
+        self.logged - self.target
+    }
+}
+
+/// Targets of each day, taking the holidays and leave declared in the
+/// config and the timesheet into account
+pub struct Schedule<'a> {
+    config: &'a Config,
+    days_off: HashMap<NaiveDate, DayOff>,
+}
+
+impl Schedule<'_> {
+    pub fn new<'a>(config: &'a Config, log_days: &LogDays) -> Schedule<'a> {
+        Schedule {
+            config,
+            days_off: log_days
+                .iter()
+                .filter_map(|day| day.off().map(|off| (day.date().naive_date(), off)))
+                .collect(),
+        }
+    }
+
+    /// Holiday or leave on the date, the timesheet takes precedence over
+    /// the config
+    pub fn day_off(&self, date: NaiveDate) -> Option<DayOff> {
+        if let Some(off) = self.days_off.get(&date) {
+            return Some(*off);
+        }
+        if self.config.holidays.iter().any(|spec| spec.contains(date)) {
+            return Some(DayOff::Holiday);
+        }
+        if self.config.leave.iter().any(|spec| spec.contains(date)) {
+            return Some(DayOff::Leave);
+        }
+        None
+    }
+
+    /// Minutes expected to be worked on the date, nothing is expected
+    /// before the targets start or on a day off
+    pub fn target_minutes(&self, date: NaiveDate) -> i64 {
+        match self.day_off(date) {
+            Some(_) => 0,
+            None => self.scheduled_minutes(date),
+        }
+    }
+
+    /// Minutes which would be expected on the date if it was not a day off
+    fn scheduled_minutes(&self, date: NaiveDate) -> i64 {
+        let targets = &self.config.targets;
+        if targets.start.is_some_and(|start| date < start) {
+            return 0;
+        }
+        let hours = match &targets.hours {
+            Some(hours) => hours[date.weekday().num_days_from_monday() as usize],
+            None if date.weekday().num_days_from_monday() < 5 => self.config.daily_target,
+            None => 0.0,
+        };
+        (hours * 60.0).round() as i64
+    }
+
+    /// Progress over the days from `start` until (excluding) `end`, days
+    /// before the targets start and after `today` do not count
+    pub fn progress(
+        &self,
+        minutes: &HashMap<NaiveDate, i64>,
+        start: NaiveDate,
+        end: NaiveDate,
+        today: NaiveDate,
+    ) -> Progress {
+        let start = match self.config.targets.start {
+            Some(targets_start) => start.max(targets_start),
+            None => start,
+        };
+        let end = end.min(today + Duration::days(1));
+        let mut progress = Progress::default();
+        let mut date = start;
+        while date < end {
+            progress.logged += minutes.get(&date).copied().unwrap_or(0);
+            progress.target += self.target_minutes(date);
+            date += Duration::days(1);
+        }
+        progress
+    }
+
+    /// Cumulative flextime balance from the configured start until
+    /// (excluding) `end`, or none if no start is configured
+    pub fn balance(
+        &self,
+        minutes: &HashMap<NaiveDate, i64>,
+        end: NaiveDate,
+        today: NaiveDate,
+    ) -> Option<i64> {
+        self.config
+            .targets
+            .start
+            .map(|start| self.progress(minutes, start, end, today).over_under())
+    }
+
+    /// Average minutes logged per day by weekday (Monday first), leaving
+    /// out days off and days without any time logged
+    pub fn average_by_weekday(&self, log_days: &LogDays) -> [Option<i64>; 7] {
+        let mut totals = [(0, 0); 7];
+        for day in log_days.iter() {
+            let date = day.date().naive_date();
+            let minutes = day.duration_total().num_minutes();
+            if minutes == 0 || self.day_off(date).is_some() {
+                continue;
+            }
+            let total = &mut totals[date.weekday().num_days_from_monday() as usize];
+            total.0 += minutes;
+            total.1 += 1;
+        }
+        totals.map(|(minutes, days)| match days {
+            0 => None,
+            _ => Some(minutes / days),
+        })
+    }
+
+    /// Number of days of each kind off from `start` until (excluding) `end`
+    pub fn days_off(&self, start: NaiveDate, end: NaiveDate) -> HashMap<DayOff, usize> {
+        let mut days_off = HashMap::new();
+        let mut date = start;
+        while date < end {
+            if let Some(off) = self.day_off(date) {
+                *days_off.entry(off).or_insert(0) += 1;
+            }
+            date += Duration::days(1);
+        }
+        days_off
+    }
+
+    /// Days of each kind off from `start` until (excluding) `end`, holidays
+    /// first, with the minutes they would otherwise have been expected
+    pub fn time_off(&self, start: NaiveDate, end: NaiveDate) -> Vec<(DayOff, usize, i64)> {
+        let mut time_off = vec![(DayOff::Holiday, 0, 0), (DayOff::Leave, 0, 0)];
+        let mut date = start;
+        while date < end {
+            if let Some(off) = self.day_off(date) {
+                let (_, days, minutes) =
+                    time_off.iter_mut().find(|(kind, ..)| *kind == off).unwrap();
+                *days += 1;
+                *minutes += self.scheduled_minutes(date);
+            }
+            date += Duration::days(1);
+        }
+        time_off.retain(|(_, days, _)| *days > 0);
+        time_off
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        app::config::{DaySpec, Targets},
+        parser::timesheet::{Date, Entry, Log, Time, TimeRange, Tokens},
+    };
+
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        // 2026-01-05 is a monday
+        NaiveDate::from_ymd(2026, 1, day)
+    }
+
+    fn config(start: Option<NaiveDate>, hours: Option<[f64; 7]>) -> Config {
+        Config {
+            targets: Targets { start, hours },
+            ..Config::empty()
+        }
+    }
+
+    fn days_off(days: &[(u32, DayOff)]) -> LogDays {
+        LogDays::new(
+            days.iter()
+                .map(|(day, off)| Entry {
+                    date: Date::from_ymd(2026, 1, *day),
+                    off: Some(*off),
+                    logs: vec![],
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn defaults_to_daily_target_on_weekdays() {
+        let config = config(None, None);
+        let schedule = Schedule::new(&config, &days_off(&[]));
+        assert_eq!(480, schedule.target_minutes(date(5)));
+        assert_eq!(480, schedule.target_minutes(date(9)));
+        assert_eq!(0, schedule.target_minutes(date(10)));
+        assert_eq!(0, schedule.target_minutes(date(11)));
+    }
+
+    #[test]
+    fn uses_hours_per_weekday_from_start() {
+        let config = config(Some(date(6)), Some([8.0, 8.0, 8.0, 8.0, 7.5, 1.0, 0.0]));
+        let schedule = Schedule::new(&config, &days_off(&[]));
+        assert_eq!(0, schedule.target_minutes(date(5)));
+        assert_eq!(480, schedule.target_minutes(date(6)));
+        assert_eq!(450, schedule.target_minutes(date(9)));
+        assert_eq!(60, schedule.target_minutes(date(10)));
+        assert_eq!(0, schedule.target_minutes(date(11)));
+    }
+
+    #[test]
+    fn no_target_on_days_off() {
+        let config = Config {
+            holidays: vec![DaySpec::Yearly { month: 1, day: 6 }],
+            leave: vec![DaySpec::Range(date(7), date(8))],
+            ..Config::empty()
+        };
+        let log_days = days_off(&[(8, DayOff::Holiday), (9, DayOff::Leave)]);
+        let schedule = Schedule::new(&config, &log_days);
+        assert_eq!(None, schedule.day_off(date(5)));
+        assert_eq!(Some(DayOff::Holiday), schedule.day_off(date(6)));
+        assert_eq!(
+            Some(DayOff::Holiday),
+            schedule.day_off(NaiveDate::from_ymd(2027, 1, 6))
+        );
+        assert_eq!(Some(DayOff::Leave), schedule.day_off(date(7)));
+        assert_eq!(Some(DayOff::Holiday), schedule.day_off(date(8)));
+        assert_eq!(0, schedule.target_minutes(date(9)));
+        assert_eq!(
+            HashMap::from([(DayOff::Holiday, 2), (DayOff::Leave, 2)]),
+            schedule.days_off(date(5), date(12))
+        );
+    }
+
+    #[test]
+    fn average_leaves_out_days_off() {
+        let config = Config::empty();
+        let entry = |day, off, hours: u32| Entry {
+            date: Date::from_ymd(2026, 1, day),
+            off,
+            logs: vec![Log {
+                time: TimeRange::from_start_end(Time::from_hm(8, 0), Time::from_hm(8 + hours, 0)),
+                description: Tokens::from_prose("foo".to_string()).into(),
+                source: None,
+            }],
+        };
+        let log_days = LogDays::new(vec![
+            entry(5, None, 6),
+            entry(12, None, 8),
+            entry(19, Some(DayOff::Holiday), 1),
+            entry(6, None, 7),
+        ]);
+        let schedule = Schedule::new(&config, &log_days);
+        assert_eq!(
+            [Some(420), Some(420), None, None, None, None, None],
+            schedule.average_by_weekday(&log_days)
+        );
+    }
+
+    #[test]
+    fn progress_ignores_future_days_and_days_before_start() {
+        let config = config(Some(date(6)), None);
+        let schedule = Schedule::new(&config, &days_off(&[]));
+        let minutes = HashMap::from([
+            (date(5), 600),
+            (date(6), 540),
+            (date(7), 400),
+            (date(10), 60),
+        ]);
+        // the week, on wednesday
+        let progress = schedule.progress(&minutes, date(5), date(12), date(7));
+        assert_eq!(
+            Progress {
+                logged: 940,
+                target: 960
+            },
+            progress
+        );
+        assert_eq!(-20, progress.over_under());
+    }
+
+    #[test]
+    fn balance_from_start() {
+        let minutes = HashMap::from([(date(5), 600), (date(6), 420), (date(10), 60)]);
+        let log_days = days_off(&[(7, DayOff::Leave)]);
+        let config = config(None, None);
+        assert_eq!(
+            None,
+            Schedule::new(&config, &log_days).balance(&minutes, date(12), date(12))
+        );
+
+        let config = self::config(Some(date(1)), None);
+        let schedule = Schedule::new(&config, &log_days);
+        assert_eq!(
+            Some(600 + 420 + 60 - 480 * 6),
+            schedule.balance(&minutes, date(12), date(11))
+        );
+        assert_eq!(
+            Some(600 - 480 * 3),
+            schedule.balance(&minutes, date(6), date(11))
+        );
+    }
+
+    #[test]
+    fn time_off_with_the_hours_it_would_have_been_worked() {
+        let log_days = days_off(&[(7, DayOff::Leave), (8, DayOff::Leave), (10, DayOff::Leave)]);
+        let config = config(None, Some([8.0, 8.0, 6.0, 8.0, 4.0, 0.0, 0.0]));
+        let schedule = Schedule::new(&config, &log_days);
+        assert_eq!(
+            vec![(DayOff::Leave, 3, 6 * 60 + 8 * 60)],
+            schedule.time_off(date(5), date(12))
+        );
+        assert_eq!(
+            Vec::<(DayOff, usize, i64)>::new(),
+            schedule.time_off(date(12), date(19))
+        );
+    }
+}
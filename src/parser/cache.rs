@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::mem;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::app::config::Config;
+
+use super::timesheet::{parse_entry, Entries, Entry};
+
+/// Days parsed before by their text, so that only the days whose text
+/// changed since are parsed again. Days not seen again by the next load are
+/// dropped.
+#[derive(Default)]
+pub struct ParseCache {
+    previous: HashMap<String, Parsed>,
+    current: HashMap<String, Parsed>,
+}
+
+struct Parsed {
+    entries: Vec<Entry>,
+    includes: Vec<String>,
+}
+
+impl ParseCache {
+    /// Parse a timesheet as `parse_entry` does, a day at a time. Text which
+    /// does not parse is an error at its line in the timesheet.
+    pub fn parse(&mut self, text: &str, config: &Config) -> Result<Entries> {
+        let mut entries = vec![];
+        let mut includes = vec![];
+        let mut line = 1;
+        for chunk in chunks(text) {
+            if !self.current.contains_key(chunk) {
+                let parsed = match self.previous.remove(chunk) {
+                    Some(parsed) => parsed,
+                    None => match parse_entry(chunk, config) {
+                        Ok(("", parsed)) => Parsed {
+                            entries: parsed.entries,
+                            includes: parsed.includes,
+                        },
+                        Ok((rest, _)) => return Err(unexpected(chunk, rest, line)),
+                        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                            return Err(unexpected(chunk, err.input, line))
+                        }
+                        Err(err) => return Err(Error::msg(err.to_string())),
+                    },
+                };
+                self.current.insert(chunk.to_string(), parsed);
+            }
+            let parsed = &self.current[chunk];
+            entries.extend(parsed.entries.iter().cloned());
+            includes.extend(parsed.includes.iter().cloned());
+            line += chunk.matches('\n').count();
+        }
+        entries.sort_by_key(|entry| entry.date.sort_value());
+        Ok(Entries { entries, includes })
+    }
+
+    /// Forget the days not parsed since the last call
+    pub fn finish(&mut self) {
+        self.previous = mem::take(&mut self.current);
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+}
+
+/// Error at the line of the timesheet where the `rest` of the chunk starting
+/// at line `first` begins
+fn unexpected(chunk: &str, rest: &str, first: usize) -> Error {
+    let offset = chunk.len() - rest.len();
+    let line = first + chunk[..offset].matches('\n').count();
+    anyhow!(
+        "line {}: unexpected \"{}\"",
+        line,
+        rest.lines().next().unwrap_or("").trim_end()
+    )
+}
+
+/// The text split before each date and include line
+fn chunks(text: &str) -> Vec<&str> {
+    let mut chunks = vec![];
+    let mut start = 0;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if offset > start && starts_chunk(line) {
+            chunks.push(&text[start..offset]);
+            start = offset;
+        }
+        offset += line.len();
+    }
+    if start < text.len() {
+        chunks.push(&text[start..]);
+    }
+    chunks
+}
+
+/// Whether the line is a date (unlike a time, the digits are followed by
+/// `-`) or an include
+fn starts_chunk(line: &str) -> bool {
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        return line[digits..].starts_with('-');
+    }
+    line.starts_with("include") || line.starts_with("!include")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TIMESHEET: &str = "include archive.log\n\
+        2026-01-05\n\
+        09:00-10:00 @acme\n\
+        \n\
+        2026-01-06 leave\n\
+        \n\
+        2026-01-07\n\
+        09:00 standup\n";
+
+    fn dates(entries: &Entries) -> Vec<String> {
+        entries
+            .entries
+            .iter()
+            .map(|entry| entry.date.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn splits_before_dates_and_includes() {
+        assert_eq!(
+            vec![
+                "include archive.log\n",
+                "2026-01-05\n09:00-10:00 @acme\n\n",
+                "2026-01-06 leave\n\n",
+                "2026-01-07\n09:00 standup\n"
+            ],
+            chunks(TIMESHEET)
+        );
+        assert_eq!(vec!["\n\n09:00\n"], chunks("\n\n09:00\n"));
+    }
+
+    #[test]
+    fn parses_like_the_whole_timesheet() {
+        let config = Config::empty();
+        let whole = parse_entry(TIMESHEET, &config).unwrap().1;
+        let mut cache = ParseCache::default();
+        let parsed = cache.parse(TIMESHEET, &config).unwrap();
+        assert_eq!(dates(&whole), dates(&parsed));
+        assert_eq!(whole.includes, parsed.includes);
+        assert_eq!(
+            whole
+                .entries
+                .iter()
+                .map(|entry| entry.logs.len())
+                .collect::<Vec<usize>>(),
+            parsed
+                .entries
+                .iter()
+                .map(|entry| entry.logs.len())
+                .collect::<Vec<usize>>()
+        );
+    }
+
+    #[test]
+    fn keeps_the_days_seen_by_the_last_parse() {
+        let config = Config::empty();
+        let mut cache = ParseCache::default();
+        cache.parse(TIMESHEET, &config).unwrap();
+        cache.finish();
+        assert_eq!(4, cache.len());
+
+        let changed = TIMESHEET.replace("09:00 standup", "09:00 standup\n10:00 review");
+        let parsed = cache.parse(&changed, &config).unwrap();
+        cache.finish();
+        assert_eq!(2, parsed.entries[2].logs.len());
+        assert_eq!(4, cache.len());
+    }
+
+    #[test]
+    fn parses_only_the_changed_days_again() {
+        let config = Config::empty();
+        let mut cache = ParseCache::default();
+        cache.parse(TIMESHEET, &config).unwrap();
+        cache.finish();
+
+        let changed = TIMESHEET.replace("09:00 standup", "09:00 standup\n10:00 review");
+        cache.parse(&changed, &config).unwrap();
+        // the other days were taken from the last parse, leaving the day as
+        // it was before the change
+        assert_eq!(
+            vec!["2026-01-07\n09:00 standup\n"],
+            cache.previous.keys().collect::<Vec<&String>>()
+        );
+        assert_eq!(4, cache.current.len());
+    }
+
+    #[test]
+    fn reports_errors_at_the_line_of_the_timesheet() {
+        let config = Config::empty();
+        let mut cache = ParseCache::default();
+        let broken = TIMESHEET.replace("09:00 standup", "09:00 standup\nstand up");
+        let err = cache.parse(&broken, &config).err().unwrap();
+        assert_eq!("line 9: unexpected \"stand up\"", err.to_string());
+
+        // the days before are cached, the line still counts them
+        let broken = broken.replace("09:00-10:00 @acme", "09:00-10:00 @acme\n10:00 review");
+        let err = cache.parse(&broken, &config).err().unwrap();
+        assert_eq!("line 10: unexpected \"stand up\"", err.to_string());
+
+        let err = cache.parse("garbage\n2026-01-05\n", &config).err().unwrap();
+        assert_eq!("line 1: unexpected \"garbage\"", err.to_string());
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::{Error, Result};
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use nom::{
+    branch::alt,
+    bytes::complete::tag_no_case,
+    character::complete::{alpha1, char, digit1, multispace0, multispace1, one_of},
+    combinator::{all_consuming, map, map_opt, map_res, opt, value},
+    sequence::{delimited, pair, preceded, separated_pair, tuple},
+};
+
+/// Resolve a date expression relative to `today`, e.g. `2026-01-31`, `-3d`,
+/// `+1w`, `last monday`, `2026-W05`, `today` or `yesterday`
+pub fn parse_date(text: &str, today: NaiveDate) -> Result<NaiveDate> {
+    match all_consuming(delimited(
+        multispace0,
+        |text| date_expression(text, today),
+        multispace0,
+    ))(text)
+    {
+        Ok((_, date)) => Ok(date),
+        Err(_) => Err(Error::msg(format!("could not parse date \"{}\"", text))),
+    }
+}
+
+/// Resolve a range of two date expressions separated by `..`, e.g.
+/// `2026-01-01..2026-03-31` or `-2w..today`. The end of the range is
+/// inclusive and returned as the day after.
+pub fn parse_date_range(text: &str, today: NaiveDate) -> Result<(NaiveDate, NaiveDate)> {
+    let (start, end) = match text.split_once("..") {
+        Some(range) => range,
+        None => return Err(Error::msg("expected a range like <start>..<end>")),
+    };
+    let start = parse_date(start, today)?;
+    let end = parse_date(end, today)?;
+    if end < start {
+        return Err(Error::msg(format!("{} is before {}", end, start)));
+    }
+    Ok((start, end + Duration::days(1)))
+}
+
+fn date_expression(text: &str, today: NaiveDate) -> nom::IResult<&str, NaiveDate> {
+    alt((
+        iso_week,
+        iso_date,
+        |text| relative(text, today),
+        |text| weekday(text, today),
+        value(today, tag_no_case("today")),
+        value(today - Duration::days(1), tag_no_case("yesterday")),
+    ))(text)
+}
+
+fn number<T: std::str::FromStr>(text: &str) -> nom::IResult<&str, T> {
+    map_res(digit1, str::parse)(text)
+}
+
+fn iso_date(text: &str) -> nom::IResult<&str, NaiveDate> {
+    map_opt(
+        tuple((number, char('-'), number, char('-'), number)),
+        |(year, _, month, _, day)| NaiveDate::from_ymd_opt(year, month, day),
+    )(text)
+}
+
+fn iso_week(text: &str) -> nom::IResult<&str, NaiveDate> {
+    map_opt(
+        separated_pair(number, tag_no_case("-W"), number),
+        |(year, week)| NaiveDate::from_isoywd_opt(year, week, Weekday::Mon),
+    )(text)
+}
+
+fn relative(text: &str, today: NaiveDate) -> nom::IResult<&str, NaiveDate> {
+    map_opt(
+        tuple((one_of("+-"), number::<u32>, one_of("dwmy"))),
+        |(sign, amount, unit)| {
+            let days = |days: i64| match sign {
+                '+' => today.checked_add_signed(Duration::days(days)),
+                _ => today.checked_sub_signed(Duration::days(days)),
+            };
+            let months = |months: u32| match sign {
+                '+' => today.checked_add_months(Months::new(months)),
+                _ => today.checked_sub_months(Months::new(months)),
+            };
+            match unit {
+                'd' => days(amount as i64),
+                'w' => days(amount as i64 * 7),
+                'm' => months(amount),
+                _ => months(amount.checked_mul(12)?),
+            }
+        },
+    )(text)
+}
+
+/// `last monday` is the monday before today, `next monday` the one after
+/// and `monday` is today if it is a monday, otherwise `last monday`
+fn weekday(text: &str, today: NaiveDate) -> nom::IResult<&str, NaiveDate> {
+    map(
+        pair(
+            opt(alt((
+                preceded(tag_no_case("last"), value(-1, multispace1)),
+                preceded(tag_no_case("next"), value(1, multispace1)),
+            ))),
+            map_res(alpha1, str::parse::<Weekday>),
+        ),
+        |(direction, weekday)| {
+            let offset = weekday.num_days_from_monday() as i64
+                - today.weekday().num_days_from_monday() as i64;
+            match direction {
+                Some(1) => today + Duration::days((offset + 6).rem_euclid(7) + 1),
+                Some(_) => today - Duration::days((-offset + 6).rem_euclid(7) + 1),
+                None => today - Duration::days((-offset).rem_euclid(7)),
+            }
+        },
+    )(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> String {
+        // a thursday
+        let today = NaiveDate::from_ymd(2026, 1, 8);
+        parse_date(text, today).unwrap().to_string()
+    }
+
+    #[test]
+    fn parses_absolute_dates() {
+        assert_eq!("2025-03-01", parse("2025-03-01"));
+        assert_eq!("2026-01-26", parse("2026-W05"));
+        assert_eq!("2025-12-29", parse("2026-w01"));
+        assert_eq!("2026-01-08", parse(" today "));
+        assert_eq!("2026-01-07", parse("Yesterday"));
+    }
+
+    #[test]
+    fn parses_relative_dates() {
+        assert_eq!("2026-01-05", parse("-3d"));
+        assert_eq!("2026-01-15", parse("+1w"));
+        assert_eq!("2025-11-08", parse("-2m"));
+        assert_eq!("2025-01-08", parse("-1y"));
+    }
+
+    #[test]
+    fn parses_weekdays() {
+        assert_eq!("2026-01-05", parse("last monday"));
+        assert_eq!("2026-01-01", parse("last thursday"));
+        assert_eq!("2026-01-08", parse("thursday"));
+        assert_eq!("2026-01-07", parse("Wed"));
+        assert_eq!("2026-01-12", parse("next monday"));
+        assert_eq!("2026-01-15", parse("next thursday"));
+    }
+
+    #[test]
+    fn parses_ranges() {
+        let today = NaiveDate::from_ymd(2026, 1, 8);
+        let (start, end) = parse_date_range("2025-10-01..2025-12-31", today).unwrap();
+        assert_eq!("2025-10-01", start.to_string());
+        assert_eq!("2026-01-01", end.to_string());
+
+        let (start, end) = parse_date_range("-1w .. today", today).unwrap();
+        assert_eq!("2026-01-01", start.to_string());
+        assert_eq!("2026-01-09", end.to_string());
+
+        assert!(parse_date_range("today", today).is_err());
+        assert!(parse_date_range("today..yesterday", today).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        let today = NaiveDate::from_ymd(2026, 1, 8);
+        assert!(parse_date("2026-02-30", today).is_err());
+        assert!(parse_date("2026-W54", today).is_err());
+        assert!(parse_date("last", today).is_err());
+        assert!(parse_date("-3x", today).is_err());
+        assert!(parse_date("today foo", today).is_err());
+    }
+
+    #[test]
+    fn rejects_overflowing_years() {
+        let today = NaiveDate::from_ymd(2026, 1, 8);
+        assert!(parse_date("-400000000y", today).is_err());
+        assert!(parse_date("+4294967295y", today).is_err());
+    }
+}
@@ -192,9 +192,12 @@ fn criteria<'a>(text: &'a str, config: &Config) -> nom::IResult<&'a str, Box<dyn
 }
 
 pub fn parse_filter(text: &str, config: &Config) -> Result<Filter> {
-    let tokens = many0(tuple((|input| criteria(input, config), multispace0)))(text);
+    let tokens = many0(tuple((|input| criteria(input, config), multispace0)))(text.trim_start());
 
     match tokens {
+        Ok((rest, _)) if !rest.trim().is_empty() => {
+            Err(Error::msg(format!("unexpected \"{}\"", rest.trim())))
+        }
         Ok(ok) => Ok(Filter {
             criterias: ok.1.into_iter().map(|criteria| criteria.0).collect(),
         }),
@@ -222,7 +225,10 @@ mod tests {
                 name: "myproject".to_string(),
                 ticket_prefix: "PROJECT-".to_string(),
                 tags: vec![],
+                rate: None,
+                recipient: None,
             }],
+            ..Config::empty()
         };
         let parsed = parse_filter("PROJECT-123", &config).unwrap();
         assert_eq!(1, parsed.criterias.len());
@@ -243,7 +249,10 @@ mod tests {
                 name: "myproject".to_string(),
                 ticket_prefix: "PROJECT-".to_string(),
                 tags: vec![],
+                rate: None,
+                recipient: None,
             }],
+            ..Config::empty()
         };
         let parsed = parse_filter("@foobar NOT PROJECT-5 PROJECT-12", &config).unwrap();
         assert_eq!(3, parsed.criterias.len());
@@ -272,4 +281,11 @@ mod tests {
             parsed.to_string()
         )
     }
+
+    #[test]
+    fn test_rejects_trailing_text() {
+        let err = parse_filter("@foobar (", &Config::empty()).err().unwrap();
+        assert_eq!("unexpected \"(\"", err.to_string());
+        assert_eq!(1, parse_filter(" @foobar ", &Config::empty()).unwrap().criterias.len());
+    }
 }
@@ -1,3 +1,5 @@
+pub mod cache;
+pub mod date;
 pub mod filter;
 pub mod timesheet;
 pub mod token;
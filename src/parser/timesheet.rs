@@ -3,11 +3,18 @@ use chrono::{NaiveDate, NaiveTime, Timelike};
 use core::fmt::Debug;
 use nom::sequence;
 use nom::{
-    character::complete::{char, digit1, line_ending, multispace0, space0},
-    combinator::{map_res, opt},
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{
+        char, digit1, line_ending, multispace0, not_line_ending, space0, space1,
+    },
+    combinator::{eof, map_res, opt, value},
     multi::many0,
     Parser,
 };
+use std::fmt::Display;
+use std::path::Path;
+use std::rc::Rc;
 
 use crate::app::config::Config;
 
@@ -49,8 +56,12 @@ impl Date {
         .parse::<i32>()
         .unwrap()
     }
-    pub fn to_string(&self) -> String {
-        format!(
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
             "{:04}-{:02}-{:02}",
             self.date.year(),
             self.date.month(),
@@ -108,8 +119,11 @@ impl Time {
     pub fn minute(&self) -> u32 {
         self.time.minute()
     }
-    pub fn to_string(&self) -> String {
-        self.time.format("%H:%M").to_string()
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.time.format("%H:%M"))
     }
 }
 
@@ -129,17 +143,14 @@ impl TimeRange {
     pub fn from_start(start: Time) -> TimeRange {
         TimeRange { start, end: None }
     }
+}
 
-    pub fn to_string(&self) -> String {
-        if self.end.is_none() {
-            return self.start.to_string();
+impl Display for TimeRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.end {
+            Some(end) => write!(f, "{}-{}", self.start, end),
+            None => write!(f, "{}", self.start),
         }
-
-        format!(
-            "{}-{}",
-            self.start.to_string(),
-            self.end.as_ref().unwrap().to_string()
-        )
     }
 }
 
@@ -151,6 +162,7 @@ impl Tokens {
         Tokens(vec![Token {
             kind: TokenKind::Prose,
             text: prose,
+            weight: None,
             whitespace: "".to_string(),
         }])
     }
@@ -158,14 +170,14 @@ impl Tokens {
         &self.0
     }
 
-    pub(crate) fn to_mut_vec(&mut self) -> &mut Vec<Token> {
-        &mut self.0
-    }
-
     pub fn len(&self) -> usize {
         self.0.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn first(&self) -> &Token {
         assert!(
             !self.0.is_empty(),
@@ -178,15 +190,6 @@ impl Tokens {
         &self.0[index]
     }
 
-    pub fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_string())
-    }
-    pub fn to_string(&self) -> String {
-        self.0.iter().fold("".to_string(), |acc, item| {
-            format!("{}{}", acc, item.to_string())
-        })
-    }
-
     pub fn new(tokens: Vec<Token>) -> Tokens {
         Tokens(tokens)
     }
@@ -207,15 +210,43 @@ impl Tokens {
     }
 }
 
+impl Display for Tokens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for token in self.0.iter() {
+            write!(f, "{}", token)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Log {
     pub time: TimeRange,
-    pub description: Tokens,
+    pub description: Rc<Tokens>,
+    /// File the log was read from
+    pub source: Option<Rc<Path>>,
+}
+
+/// A day on which no work is expected, e.g. `2026-12-25 holiday`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayOff {
+    Holiday,
+    Leave,
+}
+
+impl Display for DayOff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayOff::Holiday => write!(f, "holiday"),
+            DayOff::Leave => write!(f, "leave"),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Entry {
     pub date: Date,
+    pub off: Option<DayOff>,
     pub logs: Vec<Log>,
 }
 
@@ -228,14 +259,16 @@ impl Entry {
             date: Date {
                 date: NaiveDate::from_ymd_opt(2015, 1, 1).unwrap(),
             },
+            off: None,
             logs: vec![Log {
                 time: TimeRange {
                     start: Time::from_hm(7, 28),
                     end: Some(Time::from_hm(8, 28)),
                 },
-                description: Tokens::from_prose(
+                description: Rc::new(Tokens::from_prose(
                     "Marty! this plain text time sheet is empty Marty!".to_string(),
-                ),
+                )),
+                source: None,
             }],
         }
     }
@@ -244,6 +277,8 @@ impl Entry {
 #[derive(Debug)]
 pub struct Entries {
     pub entries: Vec<Entry>,
+    /// Paths of the `include` lines, as written
+    pub includes: Vec<String>,
 }
 
 fn date_digits_i32(text: &str) -> nom::IResult<&str, i32> {
@@ -294,8 +329,7 @@ fn time_range(text: &str) -> nom::IResult<&str, TimeRange> {
     let time_range = sequence::tuple((time, opt(sequence::pair(char('-'), time))))(text);
     match time_range {
         Ok(ok) => {
-            if (ok.1).1.is_some() {
-                let end = (ok.1).1.unwrap();
+            if let Some(end) = (ok.1).1 {
                 return Ok((
                     ok.0,
                     TimeRange {
@@ -324,17 +358,30 @@ fn log<'a>(text: &'a str, config: &Config) -> nom::IResult<&'a str, Log> {
             ok.0,
             Log {
                 time: (ok.1).0,
-                description: Tokens::new((ok.1).2),
+                description: Tokens::new((ok.1).2).into(),
+                source: None,
             },
         )),
         Err(err) => Err(err),
     }
 }
 
+fn day_off(text: &str) -> nom::IResult<&str, DayOff> {
+    sequence::preceded(
+        space1,
+        alt((
+            value(DayOff::Holiday, tag("holiday")),
+            value(DayOff::Leave, tag("leave")),
+        )),
+    )(text)
+}
+
 fn entry<'a>(text: &'a str, config: &Config) -> nom::IResult<&'a str, Entry> {
     let entry = sequence::tuple((
         date,
-        line_ending,
+        opt(day_off),
+        space0,
+        alt((line_ending, eof)),
         multispace0,
         many0(sequence::tuple((|input| log(input, config), opt(line_ending))).map(|t| t.0)),
     ))(text);
@@ -344,35 +391,73 @@ fn entry<'a>(text: &'a str, config: &Config) -> nom::IResult<&'a str, Entry> {
             ok.0,
             Entry {
                 date: (ok.1).0,
-                logs: (ok.1).3,
+                off: (ok.1).1,
+                logs: (ok.1).5,
             },
         )),
         Err(err) => Err(err),
     }
 }
+/// `include other/file.log` or `!include other/file.log` on a line of its
+/// own, between days
+fn include(text: &str) -> nom::IResult<&str, String> {
+    let include = sequence::tuple((opt(char('!')), tag("include"), space1, not_line_ending))(text);
+
+    match include {
+        Ok((rest, (_, _, _, path))) if !path.trim().is_empty() => {
+            Ok((rest, path.trim_end().to_string()))
+        }
+        Ok(_) => Err(nom::Err::Error(nom::error::Error::new(
+            text,
+            nom::error::ErrorKind::Verify,
+        ))),
+        Err(err) => Err(err),
+    }
+}
+
+enum Item {
+    Entry(Entry),
+    Include(String),
+}
+
 pub fn parse_entry<'a>(text: &'a str, config: &Config) -> nom::IResult<&'a str, Entries> {
     let entry = sequence::tuple((
         multispace0,
-        many0(sequence::tuple((|input| entry(input, config), multispace0)).map(|t| t.0)),
+        many0(
+            sequence::tuple((
+                alt((
+                    (|input| entry(input, config)).map(Item::Entry),
+                    include.map(Item::Include),
+                )),
+                multispace0,
+            ))
+            .map(|t| t.0),
+        ),
     ))(text);
 
     match entry {
         Ok(ok) => {
-            let mut entries = (ok.1).1;
+            let mut entries = vec![];
+            let mut includes = vec![];
+            for item in (ok.1).1 {
+                match item {
+                    Item::Entry(entry) => entries.push(entry),
+                    Item::Include(path) => includes.push(path),
+                }
+            }
             process_entries(&mut entries);
-            Ok((ok.0, Entries { entries }))
+            Ok((ok.0, Entries { entries, includes }))
         }
         Err(err) => Err(err),
     }
 }
 
-fn process_entries(entries: &mut Vec<Entry>) {
+fn process_entries(entries: &mut [Entry]) {
     entries.sort_by_key(|e| e.date.sort_value());
 }
 
 #[cfg(test)]
 mod tests {
-    use std::ops::Deref;
 
     use crate::app::config::Project;
 
@@ -387,8 +472,8 @@ mod tests {
     fn test_parse_date() {
         let (_, date) = date("2022-01-02").unwrap();
         assert_eq!(2022, date.year());
-        assert_eq!(01, date.month());
-        assert_eq!(02, date.day());
+        assert_eq!(1, date.month());
+        assert_eq!(2, date.day());
     }
 
     #[test]
@@ -447,6 +532,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_includes() {
+        let (_, entries) = parse_entry(
+            "include 2025.log\n\n2026-01-05\n09:00-10:00 foo\n!include archive/other file.log  \n2026-01-06\n",
+            &Config::empty(),
+        )
+        .unwrap();
+        assert_eq!(vec!["2025.log", "archive/other file.log"], entries.includes);
+        assert_eq!(2, entries.entries.len());
+        assert_eq!(1, entries.entries[0].logs.len());
+    }
+
     #[test]
     fn test_parse_entries() {
         {
@@ -522,6 +619,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_day_off() {
+        let (rest, entries) = parse_entry(
+            "2026-12-24\n09:00-12:00 Foo\n2026-12-25 holiday\n2026-12-28 leave",
+            &Config::empty(),
+        )
+        .unwrap();
+        assert_eq!("", rest);
+        assert_eq!(3, entries.entries.len());
+        assert_eq!(None, entries.entries[0].off);
+        assert_eq!(Some(DayOff::Holiday), entries.entries[1].off);
+        assert!(entries.entries[1].logs.is_empty());
+        assert_eq!(Some(DayOff::Leave), entries.entries[2].off);
+    }
+
     #[test]
     fn test_parses_time_range() {
         {
@@ -549,11 +661,11 @@ mod tests {
             );
             assert_eq!(
                 "foobar".to_string(),
-                entries.entries[0].logs[0].description.at(1).deref().text
+                entries.entries[0].logs[0].description.at(1).text
             );
             assert_eq!(
                 TokenKind::Tag,
-                entries.entries[0].logs[0].description.at(1).deref().kind
+                entries.entries[0].logs[0].description.at(1).kind
             );
         }
         {
@@ -565,16 +677,16 @@ mod tests {
             assert_eq!(1, entries.entries.len());
             assert_eq!(
                 "foobar".to_string(),
-                entries.entries[0].logs[0].description.at(1).deref().text
+                entries.entries[0].logs[0].description.at(1).text
             );
             assert_eq!(
                 TokenKind::Tag,
-                entries.entries[0].logs[0].description.at(1).deref().kind
+                entries.entries[0].logs[0].description.at(1).kind
             );
 
             assert_eq!(
                 "barfoo".to_string(),
-                entries.entries[0].logs[0].description.at(2).deref().text
+                entries.entries[0].logs[0].description.at(2).text
             );
         }
     }
@@ -588,13 +700,18 @@ mod tests {
                         name: "myproject".to_string(),
                         ticket_prefix: "PROJECT-".to_string(),
                         tags: vec![],
+                        rate: None,
+                        recipient: None,
                     },
                     Project {
                         name: "myproject".to_string(),
                         ticket_prefix: "BAR-".to_string(),
                         tags: vec![],
+                        rate: None,
+                        recipient: None,
                     },
                 ],
+                ..Config::empty()
             };
             let (_, entries) = parse_entry(
                 "2022-01-01\n20:00-21:00 BAR-12 BAZ-15 PROJECT-1 @foobar",
@@ -615,6 +732,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_weights() {
+        let config = Config {
+            projects: vec![Project {
+                name: "example".to_string(),
+                ticket_prefix: "EXAMPLE-".to_string(),
+                tags: vec![],
+                rate: None,
+                recipient: None,
+            }],
+            ..Config::empty()
+        };
+        let (_, entries) = parse_entry(
+            "2022-01-01\n20:00-21:00 EXAMPLE-1:70% EXAMPLE-2 @acme:25% foo:10%",
+            &config,
+        )
+        .unwrap();
+        let description = &entries.entries[0].logs[0].description;
+        assert_eq!(
+            ("EXAMPLE-1", Some(70)),
+            (description.at(0).text(), description.at(0).weight)
+        );
+        assert_eq!(
+            ("EXAMPLE-2", None),
+            (description.at(1).text(), description.at(1).weight)
+        );
+        assert_eq!(
+            ("acme", Some(25)),
+            (description.at(2).text(), description.at(2).weight)
+        );
+        assert_eq!(TokenKind::Prose, description.at(3).kind);
+        assert_eq!("EXAMPLE-1:70% ", description.at(0).to_string());
+    }
+
     #[test]
     fn test_parse_tag_with_space() {
         let (_, entries) = parse_entry(
@@ -626,7 +777,7 @@ mod tests {
         assert_eq!(2, entries.entries.len());
         assert_eq!(
             "barfoo".to_string(),
-            entries.entries[1].logs[0].description.at(0).deref().text
+            entries.entries[1].logs[0].description.at(0).text
         );
     }
 
@@ -639,6 +790,6 @@ mod tests {
         let description = &entries.entries[0].logs[0].description;
         assert_eq!(2, description.len());
         assert_eq!("foobar ".to_string(), description.at(0).to_string());
-        assert_eq!("barfoo".to_string(), description.at(1).deref().text);
+        assert_eq!("barfoo".to_string(), description.at(1).text);
     }
 }
@@ -1,9 +1,12 @@
+use std::fmt::Display;
+
 use nom::branch;
 use nom::bytes::complete::{self, tag};
 use nom::character::complete::char;
-use nom::character::complete::{alphanumeric1, space0};
+use nom::character::complete::{alphanumeric1, digit1, space0};
+use nom::combinator::{map_res, opt};
 use nom::error::{Error, ErrorKind};
-use nom::sequence::tuple;
+use nom::sequence::{delimited, tuple};
 
 use crate::app::config::Config;
 
@@ -18,6 +21,8 @@ pub enum TokenKind {
 pub struct Token {
     pub kind: TokenKind,
     pub text: String,
+    /// Share of the entry's duration in percent, e.g. `EXAMPLE-1:70%`
+    pub weight: Option<u32>,
     pub whitespace: String,
 }
 
@@ -26,6 +31,7 @@ impl Token {
         Token {
             kind: TokenKind::Tag,
             text,
+            weight: None,
             whitespace: "".to_string(),
         }
     }
@@ -33,6 +39,7 @@ impl Token {
         Token {
             kind: TokenKind::Prose,
             text,
+            weight: None,
             whitespace: "".to_string(),
         }
     }
@@ -40,19 +47,35 @@ impl Token {
         Token {
             kind: TokenKind::Ticket,
             text: identifier,
+            weight: None,
             whitespace: "".to_string(),
         }
     }
-    pub fn to_string(&self) -> String {
-        format!("{}{}", self.text, self.whitespace)
-    }
     pub fn text(&self) -> &str {
         &self.text
     }
 }
 
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.weight {
+            Some(weight) => write!(f, "{}:{}%{}", self.text, weight, self.whitespace),
+            None => write!(f, "{}{}", self.text, self.whitespace),
+        }
+    }
+}
+
+/// Optional share of an entry, e.g. the `:70%` of `EXAMPLE-1:70%`
+fn weight(text: &str) -> nom::IResult<&str, Option<u32>> {
+    opt(delimited(
+        char(':'),
+        map_res(digit1, str::parse::<u32>),
+        char('%'),
+    ))(text)
+}
+
 pub fn tag_token(text: &str) -> nom::IResult<&str, Token> {
-    let token = tuple((char('@'), alphanumeric1, space0))(text);
+    let token = tuple((char('@'), alphanumeric1, weight, space0))(text);
 
     match token {
         Ok(ok) => Ok((
@@ -60,7 +83,8 @@ pub fn tag_token(text: &str) -> nom::IResult<&str, Token> {
             Token {
                 kind: TokenKind::Tag,
                 text: (ok.1).1.to_string(),
-                whitespace: (ok.1).2.to_string(),
+                weight: (ok.1).2,
+                whitespace: (ok.1).3.to_string(),
             },
         )),
         Err(err) => Err(err),
@@ -72,6 +96,7 @@ pub fn ticket_token<'a>(text: &'a str, config: &Config) -> nom::IResult<&'a str,
         match tuple((
             tag::<_, _, Error<&str>>(project.ticket_prefix.as_str()),
             alphanumeric1,
+            weight,
             space0,
         ))(input)
         {
@@ -81,7 +106,8 @@ pub fn ticket_token<'a>(text: &'a str, config: &Config) -> nom::IResult<&'a str,
                     Token {
                         kind: TokenKind::Ticket,
                         text: format!("{}{}", (ok.1).0, (ok.1).1),
-                        whitespace: (ok.1).2.to_string(),
+                        weight: (ok.1).2,
+                        whitespace: (ok.1).3.to_string(),
                     },
                 ))
             }
@@ -110,6 +136,7 @@ fn prose_token(text: &str) -> nom::IResult<&str, Token> {
                 Token {
                     kind: TokenKind::Prose,
                     text: format!("{}{}", spaces1, word),
+                    weight: None,
                     whitespace: spaces2.to_string(),
                 },
             ))